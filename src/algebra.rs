@@ -1,56 +1,65 @@
 use nalgebra::base::allocator::Allocator;
 use nalgebra::base::{DefaultAllocator, DimName, VectorN};
-use num_traits::identities::One;
+use nalgebra::{ComplexField, RealField};
+use num_traits::identities::Zero;
 use std::fmt::Debug;
-use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
 
 /// A scalar type.
 ///
 /// Scalars are used for things like knot locations, weights, parameter values,
-/// and the scalar components of vector types.
-pub trait ScalarT:
-    Copy
-    + PartialOrd
-    + Debug
-    + Add<Output = Self>
-    + AddAssign
-    + Mul<Output = Self>
-    + MulAssign
-    + Sub<Output = Self>
-    + Div<Output = Self>
-    + One
-{
-}
+/// and the scalar components of vector types. Geometric algorithms require
+/// real-number operations such as square roots, so scalars must be `nalgebra`
+/// real fields. A real field is already `Copy`, ordered, and closed under the
+/// arithmetic operators, with zero and one values and conversions from
+/// primitive numbers, so no further bounds are needed.
+pub trait ScalarT: RealField {}
 
-impl<T> ScalarT for T where
-    T: Copy
-        + PartialOrd
-        + Debug
-        + Add<Output = Self>
-        + AddAssign
-        + Mul<Output = Self>
-        + MulAssign
-        + Sub<Output = Self>
-        + Div<Output = Self>
-        + One
-{
-}
+impl<T> ScalarT for T where T: RealField {}
 
 /// A vector type.
 ///
 /// Vectors are used for 3D locations like control points and points on curves
-/// or surfaces.
+/// or surfaces. They are also used for the derivatives of curves, which is why
+/// they must support subtraction and have a zero value. Their components can
+/// be accessed by index, for operations such as finding bounding boxes.
 pub trait VectorT:
-    Clone + Debug + Add<Output = Self> + Mul<<Self as VectorT>::Field, Output = Self>
+    Clone
+    + Debug
+    + Zero
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<<Self as VectorT>::Field, Output = Self>
+    + Index<usize, Output = <Self as VectorT>::Field>
+    + IndexMut<usize>
 {
     type Field: ScalarT;
+
+    /// Returns the number of components of the vector.
+    fn dimension(&self) -> usize;
+
+    /// Returns the dot product of two vectors.
+    fn dot(&self, rhs: &Self) -> Self::Field;
+
+    /// Returns the Euclidean norm (length) of the vector.
+    fn norm(&self) -> Self::Field {
+        self.dot(self).sqrt()
+    }
 }
 
 impl<N, D> VectorT for VectorN<N, D>
 where
-    N: 'static + ScalarT,
+    N: ScalarT,
     D: DimName,
     DefaultAllocator: Allocator<N, D>,
 {
     type Field = N;
+
+    fn dimension(&self) -> usize {
+        D::dim()
+    }
+
+    fn dot(&self, rhs: &Self) -> N {
+        nalgebra::Matrix::dot(self, rhs)
+    }
 }
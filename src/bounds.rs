@@ -0,0 +1,111 @@
+use crate::algebra::{ScalarT, VectorT};
+
+/// Axis-aligned bounding box.
+///
+/// The box is described by its minimum and maximum corners, which are
+/// vectors of the same type as the points it bounds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundingBox<V> {
+    min: V,
+    max: V,
+}
+
+impl<N, V> BoundingBox<V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Creates the smallest bounding box containing a sequence of points.
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the points to bound (there must be at least one)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use capstan::bounds::BoundingBox;
+    /// use nalgebra::Vector2;
+    ///
+    /// let points = [Vector2::new(1.0, 2.0), Vector2::new(-1.0, 4.0)];
+    /// let bounds = BoundingBox::from_points(points.iter());
+    /// assert_eq!(bounds.min(), &Vector2::new(-1.0, 2.0));
+    /// assert_eq!(bounds.max(), &Vector2::new(1.0, 4.0));
+    /// ```
+    pub fn from_points<'a>(mut points: impl Iterator<Item = &'a V>) -> Self
+    where
+        V: 'a,
+    {
+        let first = points.next().expect("bounds require at least one point");
+        let mut bounds = BoundingBox {
+            min: first.clone(),
+            max: first.clone(),
+        };
+        for point in points {
+            bounds.include(point);
+        }
+        bounds
+    }
+
+    /// Returns the minimum corner of the box.
+    pub fn min(&self) -> &V {
+        &self.min
+    }
+
+    /// Returns the maximum corner of the box.
+    pub fn max(&self) -> &V {
+        &self.max
+    }
+
+    /// Returns the length of the diagonal of the box.
+    pub fn diagonal(&self) -> N {
+        (self.max.clone() - self.min.clone()).norm()
+    }
+
+    /// Expands the box, if necessary, so that it contains a point.
+    pub fn include(&mut self, point: &V) {
+        for i in 0..point.dimension() {
+            self.min[i] = self.min[i].min(point[i]);
+            self.max[i] = self.max[i].max(point[i]);
+        }
+    }
+
+    /// Checks whether a point is inside the box (or on its boundary).
+    pub fn contains(&self, point: &V) -> bool {
+        (0..point.dimension()).all(|i| point[i] >= self.min[i] && point[i] <= self.max[i])
+    }
+
+    /// Checks whether two boxes overlap, or are within `tolerance` of each
+    /// other.
+    pub fn overlaps(&self, other: &Self, tolerance: N) -> bool {
+        (0..self.min.dimension()).all(|i| {
+            self.min[i] <= other.max[i] + tolerance && other.min[i] <= self.max[i] + tolerance
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    type TB = BoundingBox<Vector3<f64>>;
+
+    /// Containment of points, and overlap of boxes.
+    #[test]
+    fn overlaps_and_contains() {
+        let points = [Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 2.0, 3.0)];
+        let a = TB::from_points(points.iter());
+        assert!(a.contains(&Vector3::new(0.5, 2.0, 0.0)));
+        assert!(!a.contains(&Vector3::new(0.5, 2.5, 0.0)));
+
+        let points = [Vector3::new(1.5, 1.0, 1.0), Vector3::new(2.0, 3.0, 4.0)];
+        let mut b = TB::from_points(points.iter());
+        assert!(!a.overlaps(&b, 0.0));
+        assert!(a.overlaps(&b, 0.5));
+
+        b.include(&Vector3::new(1.0, 1.0, 1.0));
+        assert!(a.overlaps(&b, 0.0));
+        assert_eq!(b.min(), &Vector3::new(1.0, 1.0, 1.0));
+    }
+}
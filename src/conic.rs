@@ -0,0 +1,739 @@
+use crate::algebra::{ScalarT, VectorT};
+use crate::curve::Curve;
+use crate::knotvec::KnotVec;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, ConicError>;
+
+/// Creates a circle.
+///
+/// The circle is a degree 2 rational curve, made from four quarter-circle
+/// arcs, which matches the circle exactly. It starts and ends at
+/// `center + radius * x_axis`, and passes through `center + radius * y_axis`
+/// a quarter of the way around. The parameter range is `0..=1`.
+///
+/// # Parameters
+///
+/// * `center` - the center of the circle
+/// * `x_axis` - the direction from the center to the start of the circle
+/// * `y_axis` - the direction from the center to the point a quarter of the
+///   way around the circle (must be perpendicular to `x_axis`)
+/// * `radius` - the radius of the circle
+///
+/// # Example
+///
+/// ```
+/// # use capstan::conic;
+/// use nalgebra::Vector2;
+///
+/// let center = Vector2::new(1.0f64, 2.0);
+/// let circle = conic::circle(&center, &Vector2::x(), &Vector2::y(), 3.0).unwrap();
+/// let pt = circle.de_boor(0.125);
+/// assert!(((pt - center).norm() - 3.0).abs() < 1e-12);
+/// ```
+pub fn circle<N, V>(center: &V, x_axis: &V, y_axis: &V, radius: N) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    circular_arc(center, x_axis, y_axis, radius, N::zero(), N::two_pi())
+}
+
+/// Creates an arc of a circle.
+///
+/// The arc runs from the angle `start_angle` to the angle `end_angle`
+/// (measured in radians, from `x_axis` towards `y_axis`). It is a degree 2
+/// rational curve, made from equal pieces which each sweep an angle of no
+/// more than 90°, and it matches the arc exactly. The parameter range is
+/// `0..=1`.
+///
+/// This is algorithm A7.1 from The NURBS Book.
+///
+/// # Parameters
+///
+/// * `center` - the center of the circle
+/// * `x_axis` - the direction from the center at an angle of zero
+/// * `y_axis` - the direction from the center at an angle of 90° (must be
+///   perpendicular to `x_axis`)
+/// * `radius` - the radius of the circle
+/// * `start_angle` - the angle at the start of the arc
+/// * `end_angle` - the angle at the end of the arc (must be greater than
+///   `start_angle`, by no more than a full turn)
+pub fn circular_arc<N, V>(
+    center: &V,
+    x_axis: &V,
+    y_axis: &V,
+    radius: N,
+    start_angle: N,
+    end_angle: N,
+) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    elliptical_arc(
+        center,
+        x_axis,
+        y_axis,
+        radius,
+        radius,
+        start_angle,
+        end_angle,
+    )
+}
+
+/// Creates an ellipse.
+///
+/// The ellipse is a degree 2 rational curve, made from four quarter-ellipse
+/// arcs, which matches the ellipse exactly. It starts and ends at
+/// `center + x_radius * x_axis`. The parameter range is `0..=1`.
+///
+/// # Parameters
+///
+/// * `center` - the center of the ellipse
+/// * `x_axis` - the direction of the first axis of the ellipse
+/// * `y_axis` - the direction of the second axis of the ellipse (must be
+///   perpendicular to `x_axis`)
+/// * `x_radius` - the radius of the ellipse along `x_axis`
+/// * `y_radius` - the radius of the ellipse along `y_axis`
+pub fn ellipse<N, V>(
+    center: &V,
+    x_axis: &V,
+    y_axis: &V,
+    x_radius: N,
+    y_radius: N,
+) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    elliptical_arc(
+        center,
+        x_axis,
+        y_axis,
+        x_radius,
+        y_radius,
+        N::zero(),
+        N::two_pi(),
+    )
+}
+
+/// Creates an arc of an ellipse.
+///
+/// The points of the ellipse are
+/// `center + x_radius * cos(θ) * x_axis + y_radius * sin(θ) * y_axis`, and
+/// the arc runs from the angle `θ = start_angle` to `θ = end_angle`. As with
+/// `circular_arc`, it is a degree 2 rational curve made from pieces which
+/// each sweep no more than 90° of `θ`, and it matches the arc exactly. The
+/// parameter range is `0..=1`.
+///
+/// # Parameters
+///
+/// * `center` - the center of the ellipse
+/// * `x_axis` - the direction of the first axis of the ellipse
+/// * `y_axis` - the direction of the second axis of the ellipse (must be
+///   perpendicular to `x_axis`)
+/// * `x_radius` - the radius of the ellipse along `x_axis`
+/// * `y_radius` - the radius of the ellipse along `y_axis`
+/// * `start_angle` - the angle `θ` at the start of the arc
+/// * `end_angle` - the angle `θ` at the end of the arc (must be greater than
+///   `start_angle`, by no more than a full turn)
+pub fn elliptical_arc<N, V>(
+    center: &V,
+    x_axis: &V,
+    y_axis: &V,
+    x_radius: N,
+    y_radius: N,
+    start_angle: N,
+    end_angle: N,
+) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    if x_radius <= N::zero() || y_radius <= N::zero() {
+        return Err(ConicError::InvalidRadius);
+    }
+    let sweep = end_angle - start_angle;
+    let rounding = N::default_epsilon().sqrt();
+    if sweep <= N::zero() || sweep > N::two_pi() * (N::one() + rounding) {
+        return Err(ConicError::InvalidSweep);
+    }
+    let (x, y) = unit_axes(x_axis, y_axis)?;
+    let (x, y) = (x * x_radius, y * y_radius);
+    let full_turn = (sweep - N::two_pi()).abs() <= N::two_pi() * rounding;
+
+    // each piece sweeps no more than 90°; the middle control point of each
+    // piece is where the tangents at its ends meet
+    let mut n = 1;
+    while sweep > N::frac_pi_2() * N::from_usize(n).unwrap() * (N::one() + rounding) {
+        n += 1;
+    }
+    let n_pieces = N::from_usize(n).unwrap();
+    let half_sweep = sweep / (n_pieces + n_pieces);
+    let middle_weight = half_sweep.cos();
+    let point = |angle: N, scale: N| {
+        center.clone() + x.clone() * (angle.cos() * scale) + y.clone() * (angle.sin() * scale)
+    };
+
+    let mut control_points = Vec::with_capacity(2 * n + 1);
+    let mut weights = Vec::with_capacity(2 * n + 1);
+    let mut knots = vec![N::zero(); 3];
+    for i in 0..n {
+        let angle = start_angle + sweep * N::from_usize(i).unwrap() / n_pieces;
+        control_points.push(point(angle, N::one()));
+        control_points.push(point(angle + half_sweep, N::one() / middle_weight));
+        weights.push(N::one());
+        weights.push(middle_weight);
+        if i > 0 {
+            let u = N::from_usize(i).unwrap() / n_pieces;
+            knots.push(u);
+            knots.push(u);
+        }
+    }
+    if full_turn {
+        control_points.push(control_points[0].clone());
+    } else {
+        control_points.push(point(end_angle, N::one()));
+    }
+    weights.push(N::one());
+    knots.extend_from_slice(&[N::one(); 3]);
+
+    Ok(Curve::new(
+        2,
+        control_points,
+        weights,
+        KnotVec::new(knots).expect("conic knot vector must be valid"),
+    )
+    .expect("conic curve must be valid"))
+}
+
+/// Creates an arc of a parabola.
+///
+/// The points of the parabola are
+/// `vertex + (t² / (4 * focal_length)) * x_axis + t * y_axis`, so that
+/// `x_axis` points along the axis of symmetry of the parabola, towards its
+/// focus. The arc runs from `t = start` to `t = end`. It is a (non-rational)
+/// degree 2 Bézier curve, whose parameter is `t` itself.
+///
+/// # Parameters
+///
+/// * `vertex` - the vertex of the parabola
+/// * `x_axis` - the direction of the axis of symmetry of the parabola
+/// * `y_axis` - the direction of the tangent at the vertex (must be
+///   perpendicular to `x_axis`)
+/// * `focal_length` - the distance from the vertex to the focus
+/// * `start` - the value of `t` at the start of the arc
+/// * `end` - the value of `t` at the end of the arc (must be greater than
+///   `start`)
+pub fn parabolic_arc<N, V>(
+    vertex: &V,
+    x_axis: &V,
+    y_axis: &V,
+    focal_length: N,
+    start: N,
+    end: N,
+) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    if focal_length <= N::zero() {
+        return Err(ConicError::InvalidRadius);
+    }
+    if start >= end {
+        return Err(ConicError::DegenerateParameterRange);
+    }
+    let (x, y) = unit_axes(x_axis, y_axis)?;
+    let x = x * (N::one() / (focal_length * N::from_f64(4.0).unwrap()));
+
+    // the middle control point is where the tangents at the ends meet
+    let point = |a: N, b: N| {
+        vertex.clone() + x.clone() * (a * b) + y.clone() * ((a + b) / N::from_f64(2.0).unwrap())
+    };
+    let control_points = vec![point(start, start), point(start, end), point(end, end)];
+
+    let mut knots = vec![start; 3];
+    knots.extend_from_slice(&[end; 3]);
+    Ok(Curve::new(
+        2,
+        control_points,
+        vec![N::one(); 3],
+        KnotVec::new(knots).expect("conic knot vector must be valid"),
+    )
+    .expect("conic curve must be valid"))
+}
+
+/// Creates an arc of a hyperbola.
+///
+/// The points of the hyperbola are
+/// `center + x_radius * cosh(t) * x_axis + y_radius * sinh(t) * y_axis`,
+/// which is the branch of the hyperbola on the side of `x_axis`. The arc
+/// runs from `t = start` to `t = end`. It is a degree 2 rational Bézier
+/// curve which matches the arc exactly. The parameter range is `0..=1`.
+///
+/// # Parameters
+///
+/// * `center` - the center of the hyperbola, where its asymptotes meet
+/// * `x_axis` - the direction of the axis of symmetry through the vertices
+/// * `y_axis` - the direction perpendicular to `x_axis`
+/// * `x_radius` - the distance from the center to the vertex
+/// * `y_radius` - the semi-minor axis, which sets the slope of the
+///   asymptotes to `y_radius / x_radius`
+/// * `start` - the value of `t` at the start of the arc
+/// * `end` - the value of `t` at the end of the arc (must be greater than
+///   `start`)
+pub fn hyperbolic_arc<N, V>(
+    center: &V,
+    x_axis: &V,
+    y_axis: &V,
+    x_radius: N,
+    y_radius: N,
+    start: N,
+    end: N,
+) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    if x_radius <= N::zero() || y_radius <= N::zero() {
+        return Err(ConicError::InvalidRadius);
+    }
+    if start >= end {
+        return Err(ConicError::DegenerateParameterRange);
+    }
+    let (x, y) = unit_axes(x_axis, y_axis)?;
+    let (x, y) = (x * x_radius, y * y_radius);
+
+    // the middle control point is where the tangents at the ends meet, and
+    // its weight is greater than one, as for any hyperbola
+    let half = (end - start) / N::from_f64(2.0).unwrap();
+    let middle_weight = half.cosh();
+    let point = |t: N, scale: N| {
+        center.clone() + x.clone() * (t.cosh() * scale) + y.clone() * (t.sinh() * scale)
+    };
+    let control_points = vec![
+        point(start, N::one()),
+        point(start + half, N::one() / middle_weight),
+        point(end, N::one()),
+    ];
+
+    Ok(Curve::new(
+        2,
+        control_points,
+        vec![N::one(), middle_weight, N::one()],
+        KnotVec::new(vec![
+            N::zero(),
+            N::zero(),
+            N::zero(),
+            N::one(),
+            N::one(),
+            N::one(),
+        ])
+        .expect("conic knot vector must be valid"),
+    )
+    .expect("conic curve must be valid"))
+}
+
+/// Creates the arc of a circle which passes through three points.
+///
+/// The arc starts at `start`, passes through `middle`, and ends at `end`. The
+/// points may lie in any plane. As with `circular_arc`, the arc is made from
+/// pieces which each sweep no more than 90°, and its parameter range is
+/// `0..=1`.
+///
+/// An error is returned if the points are collinear (or any two of them
+/// coincide), since no circle passes through them.
+///
+/// # Parameters
+///
+/// * `start` - the point at the start of the arc
+/// * `middle` - a point on the arc between its ends
+/// * `end` - the point at the end of the arc
+///
+/// # Example
+///
+/// ```
+/// # use capstan::conic;
+/// use nalgebra::Vector2;
+///
+/// let arc = conic::three_point_arc(
+///     &Vector2::new(1.0, 0.0),
+///     &Vector2::new(0.0, 1.0),
+///     &Vector2::new(-1.0, 0.0),
+/// )
+/// .unwrap();
+/// assert_eq!(arc.control_points().len(), 5);
+/// assert!((arc.de_boor(0.5) - Vector2::new(0.0, 1.0)).norm() < 1e-12);
+/// ```
+pub fn three_point_arc<N, V>(start: &V, middle: &V, end: &V) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    // the center is equidistant from all three points, and lies in their
+    // plane: center = start + a * u + b * v
+    let u = middle.clone() - start.clone();
+    let v = end.clone() - start.clone();
+    let (uu, uv, vv) = (u.dot(&u), u.dot(&v), v.dot(&v));
+    let det = uu * vv - uv * uv;
+    if det <= N::default_epsilon().sqrt() * uu * vv {
+        return Err(ConicError::CollinearPoints);
+    }
+    let half = N::from_f64(0.5).unwrap();
+    let a = half * vv * (uu - uv) / det;
+    let b = half * uu * (vv - uv) / det;
+    let center = start.clone() + u * a + v * b;
+    let to_start = start.clone() - center.clone();
+    let radius = to_start.norm();
+
+    // the y axis lies in the plane of the points, and is chosen so that the
+    // arc reaches the middle point before its end when sweeping from the x
+    // axis towards the y axis
+    let x_axis = to_start * (N::one() / radius);
+    let to_middle = middle.clone() - center.clone();
+    let to_end = end.clone() - center.clone();
+    let perpendicular = |w: &V| w.clone() - x_axis.clone() * w.dot(&x_axis);
+    let (middle_side, end_side) = (perpendicular(&to_middle), perpendicular(&to_end));
+    let mut y_axis = if middle_side.norm() >= end_side.norm() {
+        unit(&middle_side)?
+    } else {
+        unit(&end_side)?
+    };
+    let mut end_angle = angle_around(&x_axis, &y_axis, &to_end);
+    if angle_around(&x_axis, &y_axis, &to_middle) > end_angle {
+        y_axis = y_axis * -N::one();
+        end_angle = N::two_pi() - end_angle;
+    }
+
+    circular_arc(&center, &x_axis, &y_axis, radius, N::zero(), end_angle)
+}
+
+/// Returns the angle of a vector from `x_axis` towards `y_axis`, in the
+/// range `0..2π`.
+fn angle_around<N, V>(x_axis: &V, y_axis: &V, point: &V) -> N
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let angle = point.dot(y_axis).atan2(point.dot(x_axis));
+    if angle < N::zero() {
+        angle + N::two_pi()
+    } else {
+        angle
+    }
+}
+
+/// Normalizes an axis vector.
+fn unit<N, V>(axis: &V) -> Result<V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let length = axis.norm();
+    if length > N::zero() {
+        Ok(axis.clone() * (N::one() / length))
+    } else {
+        Err(ConicError::DegenerateAxis)
+    }
+}
+
+/// Normalizes a pair of axis vectors, which must be perpendicular to each
+/// other (to within rounding errors).
+fn unit_axes<N, V>(x_axis: &V, y_axis: &V) -> Result<(V, V)>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let (x, y) = (unit(x_axis)?, unit(y_axis)?);
+    if x.dot(&y).abs() > N::default_epsilon().sqrt() {
+        return Err(ConicError::AxesNotPerpendicular);
+    }
+    Ok((x, y))
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ConicError {
+    #[error("radius or focal length must be greater than zero")]
+    InvalidRadius,
+
+    #[error("axis vector has zero length")]
+    DegenerateAxis,
+
+    #[error("axis vectors are not perpendicular")]
+    AxesNotPerpendicular,
+
+    #[error("sweep angle must be greater than zero, and at most a full turn")]
+    InvalidSweep,
+
+    #[error("parameter range is empty; the start must be less than the end")]
+    DegenerateParameterRange,
+
+    #[error("points are collinear, so no circular arc passes through them")]
+    CollinearPoints,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{Vector2, Vector3};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    /// Checks that a curve lies on a circle, by sampling points along it.
+    fn assert_on_circle(curve: &Curve<f64, Vector3<f64>>, center: &Vector3<f64>, radius: f64) {
+        for i in 0..101 {
+            let pt = curve.de_boor((i as f64) / 100.0);
+            assert_relative_eq!((pt - center).norm(), radius, epsilon = 1e-12);
+        }
+    }
+
+    /// A circle has the standard control points of a nine-point circle.
+    #[test]
+    fn circle() {
+        let circle =
+            super::circle(&Vector2::new(0.0, 0.0), &Vector2::x(), &Vector2::y(), 1.0).unwrap();
+        let r = f64::sqrt(2.0) / 2.0;
+        let expected = [
+            (1.0, 0.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+            (-1.0, 1.0),
+            (-1.0, 0.0),
+            (-1.0, -1.0),
+            (0.0, -1.0),
+            (1.0, -1.0),
+            (1.0, 0.0),
+        ];
+        for (cp, &(x, y)) in circle.control_points().iter().zip(expected.iter()) {
+            assert_relative_eq!(cp, &Vector2::new(x, y), epsilon = 1e-12);
+        }
+        assert_eq!(circle.control_points()[0], circle.control_points()[8]);
+        for (i, &w) in circle.weights().iter().enumerate() {
+            assert_relative_eq!(w, if i % 2 == 0 { 1.0 } else { r }, epsilon = 1e-12);
+        }
+        assert_eq!(
+            circle.knots().as_slice(),
+            &[0.0, 0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1.0, 1.0, 1.0]
+        );
+
+        // a circle in a tilted plane
+        let center = Vector3::new(1.0, 2.0, 3.0);
+        let x_axis = Vector3::new(1.0, 1.0, 0.0);
+        let y_axis = Vector3::new(-1.0, 1.0, 1.0);
+        let circle = super::circle(&center, &x_axis, &y_axis, 2.5).unwrap();
+        assert_on_circle(&circle, &center, 2.5);
+        assert_relative_eq!(
+            circle.de_boor(0.25),
+            center + y_axis.normalize() * 2.5,
+            epsilon = 1e-12
+        );
+    }
+
+    /// Circular arcs are split into pieces of no more than 90°.
+    #[test]
+    fn circular_arc() {
+        let center = Vector3::new(0.0, 0.0, 1.0);
+        let (x_axis, y_axis) = (Vector3::x(), Vector3::y());
+        for &(start, end, pieces) in &[
+            (0.0, PI / 6.0, 1),
+            (-PI / 4.0, PI / 4.0, 1),
+            (0.0, 100f64.to_radians(), 2),
+            (1.0, 1.0 + PI, 2),
+            (0.5, 0.5 + 3.0 * FRAC_PI_2, 3),
+            (-2.0, -2.0 + 2.0 * PI, 4),
+        ] {
+            let arc = super::circular_arc(&center, &x_axis, &y_axis, 2.0, start, end).unwrap();
+            assert_eq!(arc.control_points().len(), 2 * pieces + 1);
+            assert_on_circle(&arc, &center, 2.0);
+            let point = |angle: f64| center + Vector3::new(angle.cos(), angle.sin(), 0.0) * 2.0;
+            assert_relative_eq!(arc.de_boor(0.0), point(start), epsilon = 1e-12);
+            assert_relative_eq!(arc.de_boor(1.0), point(end), epsilon = 1e-12);
+
+            // the arc sweeps steadily around the circle
+            let length = arc.arc_length(0.0, 1.0, 1e-10).unwrap();
+            assert_relative_eq!(length, 2.0 * (end - start), epsilon = 1e-9);
+        }
+
+        assert_eq!(
+            super::circular_arc(&center, &x_axis, &y_axis, 2.0, 1.0, 1.0),
+            Err(ConicError::InvalidSweep)
+        );
+        assert_eq!(
+            super::circular_arc(&center, &x_axis, &y_axis, 2.0, 0.0, 7.0),
+            Err(ConicError::InvalidSweep)
+        );
+        assert_eq!(
+            super::circular_arc(&center, &x_axis, &y_axis, 0.0, 0.0, 1.0),
+            Err(ConicError::InvalidRadius)
+        );
+        assert_eq!(
+            super::circular_arc(&center, &Vector3::zeros(), &y_axis, 1.0, 0.0, 1.0),
+            Err(ConicError::DegenerateAxis)
+        );
+        assert_eq!(
+            super::circular_arc(
+                &center,
+                &x_axis,
+                &Vector3::new(1.0, 1.0, 0.0),
+                1.0,
+                0.0,
+                1.0
+            ),
+            Err(ConicError::AxesNotPerpendicular)
+        );
+    }
+
+    /// Points on ellipses satisfy the equation of the ellipse.
+    #[test]
+    fn ellipse() {
+        let center = Vector2::new(-1.0, 2.0);
+        let (x_axis, y_axis) = (Vector2::new(3.0, 4.0), Vector2::new(-4.0, 3.0));
+        let local = |pt: Vector2<f64>| {
+            let d = pt - center;
+            Vector2::new(d.dot(&x_axis) / 5.0, d.dot(&y_axis) / 5.0)
+        };
+
+        let ellipse = super::ellipse(&center, &x_axis, &y_axis, 3.0, 1.5).unwrap();
+        assert_eq!(ellipse.control_points().len(), 9);
+        for i in 0..101 {
+            let pt = local(ellipse.de_boor((i as f64) / 100.0));
+            assert_relative_eq!(
+                (pt.x / 3.0).powi(2) + (pt.y / 1.5).powi(2),
+                1.0,
+                epsilon = 1e-12
+            );
+        }
+
+        let arc = super::elliptical_arc(&center, &x_axis, &y_axis, 3.0, 1.5, 0.3, 2.8).unwrap();
+        assert_eq!(arc.control_points().len(), 5);
+        assert_relative_eq!(
+            local(arc.de_boor(0.0)),
+            Vector2::new(3.0 * 0.3f64.cos(), 1.5 * 0.3f64.sin()),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            local(arc.de_boor(1.0)),
+            Vector2::new(3.0 * 2.8f64.cos(), 1.5 * 2.8f64.sin()),
+            epsilon = 1e-12
+        );
+        for i in 0..101 {
+            let pt = local(arc.de_boor((i as f64) / 100.0));
+            assert_relative_eq!(
+                (pt.x / 3.0).powi(2) + (pt.y / 1.5).powi(2),
+                1.0,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    /// Parabolic arcs are parameterized by the distance along the tangent at
+    /// the vertex.
+    #[test]
+    fn parabolic_arc() {
+        let vertex = Vector2::new(1.0, 1.0);
+        let arc =
+            super::parabolic_arc(&vertex, &Vector2::y(), &Vector2::x(), 0.5, -1.0, 3.0).unwrap();
+        assert_eq!(arc.weights(), &vec![1.0; 3]);
+        for i in 0..41 {
+            let t = -1.0 + (i as f64) / 10.0;
+            let expected = vertex + Vector2::new(t, t * t / 2.0);
+            assert_relative_eq!(arc.de_boor(t), expected, epsilon = 1e-12);
+        }
+
+        assert_eq!(
+            super::parabolic_arc(&vertex, &Vector2::y(), &Vector2::x(), 0.5, 1.0, 1.0),
+            Err(ConicError::DegenerateParameterRange)
+        );
+        assert_eq!(
+            super::parabolic_arc(&vertex, &Vector2::y(), &Vector2::x(), -0.5, 0.0, 1.0),
+            Err(ConicError::InvalidRadius)
+        );
+        assert_eq!(
+            super::parabolic_arc(
+                &vertex,
+                &Vector2::y(),
+                &Vector2::new(1.0, 0.1),
+                0.5,
+                0.0,
+                1.0
+            ),
+            Err(ConicError::AxesNotPerpendicular)
+        );
+    }
+
+    /// Points on hyperbolic arcs satisfy the equation of the hyperbola.
+    #[test]
+    fn hyperbolic_arc() {
+        let arc = super::hyperbolic_arc(
+            &Vector2::new(0.0, 0.0),
+            &Vector2::x(),
+            &Vector2::y(),
+            2.0,
+            0.5,
+            -1.0,
+            2.0,
+        )
+        .unwrap();
+        assert!(arc.weights()[1] > 1.0);
+        assert_relative_eq!(
+            arc.de_boor(0.0),
+            Vector2::new(2.0 * 1f64.cosh(), -0.5 * 1f64.sinh()),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            arc.de_boor(1.0),
+            Vector2::new(2.0 * 2f64.cosh(), 0.5 * 2f64.sinh()),
+            epsilon = 1e-12
+        );
+        for i in 0..101 {
+            let pt = arc.de_boor((i as f64) / 100.0);
+            assert!(pt.x > 0.0);
+            assert_relative_eq!(
+                (pt.x / 2.0).powi(2) - (pt.y / 0.5).powi(2),
+                1.0,
+                epsilon = 1e-9
+            );
+        }
+    }
+
+    /// Arcs through three points, in both directions and of any sweep.
+    #[test]
+    fn three_point_arc() {
+        let center = Vector3::new(1.0, -1.0, 2.0);
+        let (x_axis, y_axis) = (Vector3::new(0.0, 0.6, 0.8), Vector3::new(1.0, 0.0, 0.0));
+        let point = |angle: f64| center + (x_axis * angle.cos() + y_axis * angle.sin()) * 3.0;
+        for &(a, b, c) in &[
+            (0.0, 0.5, 1.0),
+            (0.0, 1.0, 3.0),
+            (0.0, PI, 4.0),
+            (0.0, 3.0, 6.0),
+            (1.0, 0.5, 0.0),
+            (6.0, 3.0, 0.5),
+            (0.0, -PI, -5.0),
+        ] {
+            let (start, middle, end) = (point(a), point(b), point(c));
+            let arc = super::three_point_arc(&start, &middle, &end).unwrap();
+            assert_on_circle(&arc, &center, 3.0);
+            assert_relative_eq!(arc.de_boor(0.0), start, epsilon = 1e-9);
+            assert_relative_eq!(arc.de_boor(1.0), end, epsilon = 1e-9);
+            let (_, _, distance) = arc.closest_point(&middle, 1e-12);
+            assert!(distance < 1e-9);
+            assert_relative_eq!(
+                arc.arc_length(0.0, 1.0, 1e-10).unwrap(),
+                3.0 * (c - a).abs(),
+                epsilon = 1e-8
+            );
+        }
+
+        let (a, b) = (Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0));
+        assert_eq!(
+            super::three_point_arc(&a, &b, &Vector2::new(2.0, 2.0)),
+            Err(ConicError::CollinearPoints)
+        );
+        assert_eq!(
+            super::three_point_arc(&a, &b, &b),
+            Err(ConicError::CollinearPoints)
+        );
+    }
+}
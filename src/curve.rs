@@ -1,13 +1,37 @@
 use crate::algebra::{ScalarT, VectorT};
 use crate::knotvec::KnotVec;
+use nalgebra::base::allocator::Allocator;
+use nalgebra::base::dimension::{DimName, DimNameAdd, DimNameSum, U1};
+use nalgebra::base::{DefaultAllocator, MatrixN, VectorN};
+use nalgebra::Point;
+use std::ops::{Add, Mul, Sub};
 use thiserror::Error;
 
+mod approximate;
+mod arclength;
+mod banded;
+mod bernstein;
+mod bounds;
+mod closest;
+mod differential;
+mod fair;
+mod interpolate;
+mod intersect;
+mod local;
+
+pub use arclength::ArcLengthTable;
+pub use differential::Frame;
+pub use fair::FairingEnergy;
+pub use interpolate::{DerivativeConstraint, Parameterization};
+pub use intersect::CurveIntersection;
+pub use local::TangentEstimate;
+
 pub type Result<T> = std::result::Result<T, CurveError>;
 
 /// NURBS curve.
 ///
 /// Non-Uniform Rational B-Spline.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Curve<N, V>
 where
     N: ScalarT,
@@ -19,6 +43,27 @@ where
     knots: KnotVec<N>,
 }
 
+/// The form of the knot vector of a curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveKind {
+    /// The first and last knots are repeated `degree + 1` times, so that the
+    /// curve starts at its first control point and ends at its last control
+    /// point.
+    Clamped,
+
+    /// The knot vector is not clamped, so the ends of the curve do not, in
+    /// general, coincide with any control points.
+    Unclamped,
+
+    /// An unclamped curve which is closed and smooth: its last `degree`
+    /// control points and weights repeat the first `degree`, and the spacing
+    /// of its knots repeats with the same period, so the curve joins its
+    /// start with continuous derivatives up to order `degree - 1`. This is
+    /// how closed curves are commonly stored by other CAD software, such as
+    /// Rhino and OpenNURBS.
+    Periodic,
+}
+
 impl<N, V> Curve<N, V>
 where
     N: ScalarT,
@@ -31,20 +76,23 @@ where
     /// * `control_points.len() > degree`
     /// * `weights.len() == control_points.len()`
     /// * `knots.len() == degree + control_points.len() + 1`
-    /// * `knots.is_clamped()`
+    /// * `knots[degree] < knots[control_points.len()]`
     ///
-    /// The NURBS curves represented here are clamped (ie. they must have a
-    /// knot multiplicity at either end equal to the degree plus one).
-    /// Un-clamped curves can be converted to clamped ones via knot insertion.
+    /// The knot vector may be clamped (with a knot multiplicity at either end
+    /// equal to the degree plus one), unclamped, or periodic (see
+    /// `CurveKind`). The curve is defined over the range of parameter values
+    /// from `knots[degree]` to `knots[control_points.len()]`, which for a
+    /// clamped curve is the whole of the knot vector. Unclamped curves can be
+    /// converted to clamped ones using `clamp`.
     ///
     /// Parameters:
     ///
     /// * `degree` - polynomial degree of the NURBS curve
     /// * `control_points` - vector of control points
     /// * `weights` - vector of weights (must be the same length as
-    ///               `control_points`)
+    ///   `control_points`)
     /// * `knots` - knot vector (must have `degree + control_points.len() + 1`
-    ///             elements)
+    ///   elements)
     pub fn new(
         degree: usize,
         control_points: Vec<V>,
@@ -65,8 +113,8 @@ where
                 required_knot_len: degree + control_points.len() + 1,
                 receieved_knot_len: knots.len(),
             })
-        } else if !knots.is_clamped(degree) {
-            Err(CurveError::KnotVectorNotClamped)
+        } else if knots[degree] >= knots[control_points.len()] {
+            Err(CurveError::DegenerateParameterRange)
         } else {
             Ok(Curve {
                 degree,
@@ -86,16 +134,16 @@ where
     /// is numerically stable.
     ///
     /// The parameter `u` is clamped to the allowed range of the parameter
-    /// space of the curve (which is the range from `self.knots().min_u()` to
-    /// `self.knots().max_u()` inclusive).
+    /// space of the curve (which is the range from `self.min_u()` to
+    /// `self.max_u()` inclusive).
     ///
     /// # Parameters
     ///
     /// * `u` - the parameter value at which to evaluate the NURBS curve
     pub fn de_boor(&self, u: N) -> V {
         // clamp u and find the knot span containing u
-        let uu = self.knots.clamp(u);
-        let k = self.knots.find_span(uu);
+        let uu = self.clamp_param(u);
+        let k = self.find_span(uu);
 
         // populate initial triangular column
         let mut d = Vec::<V>::with_capacity(self.degree + 1); // homogeneous points
@@ -128,202 +176,2693 @@ where
         d[self.degree].clone() * (N::one() / dw[self.degree])
     }
 
-    /// Returns the vector of control points.
-    pub fn control_points(&self) -> &Vec<V> {
-        &self.control_points
-    }
+    /// Evaluates the curve and its derivatives at a parameter value.
+    ///
+    /// Returns a vector of `n + 1` elements, where element `k` is the `k`th
+    /// derivative of the curve with respect to its parameter, evaluated at
+    /// `u`. Element `0` is the point on the curve itself.
+    ///
+    /// The derivatives are computed analytically. The derivatives of the
+    /// homogeneous (weighted) curve are found from the derivatives of the
+    /// B-spline basis functions, and the derivatives of the rational curve
+    /// are then recovered from these using the quotient rule (algorithm A4.2
+    /// from The NURBS Book). Because the curve is rational, derivatives of
+    /// order greater than the degree are not, in general, zero.
+    ///
+    /// As with `de_boor`, the parameter `u` is clamped to the allowed range
+    /// of the parameter space of the curve. At a knot, the derivatives are
+    /// those of the span which starts at the knot (except at `max_u`, where
+    /// the final span is used).
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the parameter value at which to evaluate the derivatives
+    /// * `n` - the highest order of derivative to compute
+    pub fn derivatives(&self, u: N, n: usize) -> Vec<V> {
+        let p = self.degree;
+        let uu = self.clamp_param(u);
+        let span = self.find_span(uu);
+        let ders = self.knots.basis_function_derivatives(p, span, uu, n);
 
-    /// Returns the knot vector.
-    pub fn knots(&self) -> &KnotVec<N> {
-        &self.knots
+        // derivatives of the homogeneous curve; the point components are
+        // accumulated separately from the weight components
+        let mut aders = Vec::<V>::with_capacity(n + 1);
+        let mut wders = Vec::<N>::with_capacity(n + 1);
+        for ders_k in &ders {
+            let mut a = V::zero();
+            let mut w = N::zero();
+            for (j, n_kj) in ders_k.iter().enumerate() {
+                let i = span - p + j;
+                let nw = *n_kj * self.weights[i];
+                a = a + self.control_points[i].clone() * nw;
+                w += nw;
+            }
+            aders.push(a);
+            wders.push(w);
+        }
+
+        // apply the quotient rule to find the derivatives of the rational
+        // curve
+        let mut ck = Vec::<V>::with_capacity(n + 1);
+        for k in 0..n + 1 {
+            let mut v = aders[k].clone();
+            for i in 1..k + 1 {
+                v = v - ck[k - i].clone() * (binomial::<N>(k, i) * wders[i]);
+            }
+            ck.push(v * (N::one() / wders[0]));
+        }
+
+        ck
     }
 
-    /// Scale the curve by a uniform amount about the origin.
+    /// Inserts a knot into the curve.
+    ///
+    /// Returns a new curve which is geometrically identical to this one, but
+    /// which has the knot value `u` inserted `times` times into its knot
+    /// vector. The control points and weights are updated accordingly, using
+    /// Boehm's algorithm (algorithm A5.1 from The NURBS Book). Inserting a
+    /// knot `times` times adds `times` control points to the curve.
+    ///
+    /// The multiplicity of a knot cannot exceed the degree of the curve, so
+    /// an error is returned if the existing multiplicity of `u` plus `times`
+    /// would be greater than the degree. This also means that knots cannot be
+    /// inserted at the ends of the parameter range of a clamped curve, where
+    /// the multiplicity is already `degree + 1`.
+    ///
+    /// # Parameters
     ///
-    /// NOTE: This method will probably be replaced by a more general
-    ///       transformation method in the future.
-    pub fn uniform_scale(&mut self, scale_factor: N) {
-        for cp in &mut self.control_points {
-            *cp = cp.clone() * scale_factor;
+    /// * `u` - the knot value to insert
+    /// * `times` - the number of times to insert the knot
+    pub fn insert_knot(&self, u: N, times: usize) -> Result<Self> {
+        if u < self.min_u() || u > self.max_u() {
+            return Err(CurveError::ParameterOutOfRange);
+        }
+        let p = self.degree;
+        let s = self.knots.multiplicity(u);
+        if s + times > p {
+            return Err(CurveError::KnotMultiplicityExceedsDegree {
+                degree: p,
+                multiplicity: s + times,
+            });
+        }
+        if times == 0 {
+            return Ok(self.clone());
+        }
+
+        let k = self.knots.find_span(u);
+        let pw = self.homogeneous_control_points();
+
+        // unaffected control points are copied across from either end
+        let mut qw = vec![pw[0].clone(); pw.len() + times];
+        qw[..k - p + 1].clone_from_slice(&pw[..k - p + 1]);
+        qw[k - s + times..].clone_from_slice(&pw[k - s..]);
+
+        // affected control points are found by repeated linear interpolation
+        let mut rw: Vec<Homogeneous<N, V>> = pw[k - p..k - s + 1].to_vec();
+        let mut l = k - p;
+        for j in 1..times + 1 {
+            l = k - p + j;
+            for i in 0..p - j - s + 1 {
+                let alpha = (u - self.knots[l + i]) / (self.knots[i + k + 1] - self.knots[l + i]);
+                rw[i] = rw[i].lerp(&rw[i + 1], alpha);
+            }
+            qw[l] = rw[0].clone();
+            qw[k + times - j - s] = rw[p - j - s].clone();
         }
+        if l + 1 < k - s {
+            qw[l + 1..k - s].clone_from_slice(&rw[1..k - s - l]);
+        }
+
+        Ok(Curve::from_homogeneous(p, qw, self.knots.insert(u, times)))
     }
-}
 
-#[derive(Error, Debug, PartialEq)]
-pub enum CurveError {
-    #[error("invalid degree; must satisfy degree > 0")]
-    InvalidDegree,
+    /// Inserts many knots into the curve in a single pass.
+    ///
+    /// Returns a new curve which is geometrically identical to this one, but
+    /// with all of the knot values in `new_knots` inserted into its knot
+    /// vector. This is equivalent to calling `insert_knot` once for each new
+    /// knot, but it is much more efficient when inserting many knots. It uses
+    /// the knot refinement algorithm (algorithm A5.4 from The NURBS Book).
+    ///
+    /// The new knots must be sorted in non-decreasing order, and values may
+    /// be repeated to insert a knot multiple times. As with `insert_knot`, the
+    /// final multiplicity of any knot cannot exceed the degree of the curve.
+    ///
+    /// # Parameters
+    ///
+    /// * `new_knots` - sorted slice of knot values to insert
+    pub fn refine_knots(&self, new_knots: &[N]) -> Result<Self> {
+        if !new_knots.is_sorted() {
+            return Err(CurveError::UnsortedKnots);
+        }
+        let p = self.degree;
+        for (i, &x) in new_knots.iter().enumerate() {
+            if x < self.min_u() || x > self.max_u() {
+                return Err(CurveError::ParameterOutOfRange);
+            }
+            if i == 0 || new_knots[i - 1] != x {
+                let multiplicity = self.knots.multiplicity(x)
+                    + new_knots[i..].iter().take_while(|&&y| y == x).count();
+                if multiplicity > p {
+                    return Err(CurveError::KnotMultiplicityExceedsDegree {
+                        degree: p,
+                        multiplicity,
+                    });
+                }
+            }
+        }
+        if new_knots.is_empty() {
+            return Ok(self.clone());
+        }
 
-    #[error("N={} control points were supplied; at least {} are required \
-             for a degree {} curve",
-            .number_supplied,
-            .degree,
-            .degree - 1)]
-    InsufficientControlPoints {
-        degree: usize,
-        number_supplied: usize,
-    },
+        let pw = self.homogeneous_control_points();
+        let n = pw.len() - 1;
+        let m = n + p + 1;
+        let r = new_knots.len() - 1;
+        let a = self.find_span(new_knots[0]);
+        let b = self.find_span(new_knots[r]) + 1;
 
-    #[error("number of weights and control points must be identical")]
-    MismatchedWeightsAndControlPoints,
+        // unaffected control points and knots are copied across from either
+        // end
+        let mut qw = vec![pw[0].clone(); pw.len() + r + 1];
+        let mut ubar = vec![self.knots[0]; m + r + 2];
+        qw[..a - p + 1].clone_from_slice(&pw[..a - p + 1]);
+        qw[b + r..].clone_from_slice(&pw[b - 1..]);
+        let knots = self.knots.as_slice();
+        ubar[..a + 1].copy_from_slice(&knots[..a + 1]);
+        ubar[b + p + r + 1..].copy_from_slice(&knots[b + p..]);
 
-    #[error("expected {} knot values, but received {}",
-            .required_knot_len,
-            .receieved_knot_len)]
-    InvalidKnotCount {
-        required_knot_len: usize,
-        receieved_knot_len: usize,
-    },
+        // work backwards through the new knots, inserting each one
+        let mut i = b + p - 1;
+        let mut k = b + p + r;
+        for &x in new_knots.iter().rev() {
+            while x <= self.knots[i] && i > a {
+                qw[k - p - 1] = pw[i - p - 1].clone();
+                ubar[k] = self.knots[i];
+                k -= 1;
+                i -= 1;
+            }
+            qw[k - p - 1] = qw[k - p].clone();
+            for l in 1..p + 1 {
+                let ind = k - p + l;
+                let alpha = ubar[k + l] - x;
+                if alpha == N::zero() {
+                    qw[ind - 1] = qw[ind].clone();
+                } else {
+                    let alpha = alpha / (ubar[k + l] - self.knots[i + l - p]);
+                    qw[ind - 1] = qw[ind].lerp(&qw[ind - 1], alpha);
+                }
+            }
+            ubar[k] = x;
+            k -= 1;
+        }
 
-    #[error("knot vector was not clamped")]
-    KnotVectorNotClamped,
-}
+        Ok(Curve::from_homogeneous(
+            p,
+            qw,
+            KnotVec::new(ubar).expect("refined knot vector should be valid"),
+        ))
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use approx::assert_relative_eq;
-    use nalgebra::Vector2;
+    /// Removes a knot from the curve, if possible within a tolerance.
+    ///
+    /// Attempts to remove the knot value `u` from the curve `times` times,
+    /// using algorithm A5.8 from The NURBS Book. A knot can be removed exactly
+    /// only if the curve has a higher order of continuity at the knot than its
+    /// multiplicity implies; otherwise removing it changes the shape of the
+    /// curve. Each removal is only performed if the resulting curve remains
+    /// within a distance `tolerance` of the original curve (the distance is
+    /// measured between points at the same parameter value, so it bounds the
+    /// geometric distance between the curves). Removal stops at the first
+    /// knot which cannot be removed within the tolerance.
+    ///
+    /// Returns the new curve and the number of knots that were actually
+    /// removed, which may be less than `times`.
+    ///
+    /// Only knots strictly inside the parameter range of the curve can be
+    /// removed, so that the parameter range is unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the knot value to remove
+    /// * `times` - the maximum number of times to remove the knot
+    /// * `tolerance` - the maximum allowed distance from the original curve
+    pub fn remove_knot(&self, u: N, times: usize, tolerance: N) -> Result<(Self, usize)> {
+        if u <= self.min_u() || u >= self.max_u() {
+            return Err(CurveError::ParameterOutOfRange);
+        }
+        if self.knots.multiplicity(u) == 0 {
+            return Err(CurveError::KnotNotFound);
+        }
 
-    /// Test Curve
-    type TC = Curve<f32, Vector2<f32>>;
+        let mut curve = self.clone();
+        let mut removed = 0;
+        let mut deviation = N::zero();
+        while removed < times && curve.knots.multiplicity(u) > 0 {
+            match curve.remove_knot_once(u, tolerance - deviation) {
+                Some((c, e)) => {
+                    curve = c;
+                    deviation += e;
+                    removed += 1;
+                }
+                None => break,
+            }
+        }
 
-    /// The degree of a NURBS curve must be >= 0.
-    #[test]
-    fn invalid_degree() {
-        let result = TC::new(0, vec![], vec![], KnotVec::new(vec![0.0, 1.0]).unwrap());
-        assert_eq!(result, Err(CurveError::InvalidDegree));
+        Ok((curve, removed))
     }
 
-    /// There must be at least degree + 1 control points.
-    #[test]
-    fn insufficient_control_points() {
-        let result = TC::new(
-            1,
-            vec![Vector2::new(0.0, 0.0)],
-            vec![1.0],
-            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
-        );
-        assert_eq!(
-            result,
-            Err(CurveError::InsufficientControlPoints {
-                degree: 1,
-                number_supplied: 1
-            })
-        )
+    /// Removes all knots which can be removed within a tolerance.
+    ///
+    /// Every interior knot of the curve is visited in turn, and as many copies
+    /// of it as possible are removed (see `remove_knot`). The tolerance is
+    /// shared between all removals, so that the final curve remains within a
+    /// distance `tolerance` of the original curve. This is useful for
+    /// removing redundant knots from a curve.
+    ///
+    /// Returns the new curve and the number of knots that were removed.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the maximum allowed distance from the original curve
+    pub fn simplify_knots(&self, tolerance: N) -> (Self, usize) {
+        let mut curve = self.clone();
+        let mut removed = 0;
+        let mut deviation = N::zero();
+        for (u, _) in self.interior_knots() {
+            while curve.knots.multiplicity(u) > 0 {
+                match curve.remove_knot_once(u, tolerance - deviation) {
+                    Some((c, e)) => {
+                        curve = c;
+                        deviation += e;
+                        removed += 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        (curve, removed)
     }
 
-    /// The number of control points and weights must be identical.
-    #[test]
-    fn weights_and_cps_lengths_must_be_equal() {
-        let result = TC::new(
-            1,
-            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
-            vec![1.0],
-            KnotVec::new(vec![0.0, 1.0]).unwrap(),
-        );
-        assert_eq!(result, Err(CurveError::MismatchedWeightsAndControlPoints));
+    /// Removes a single copy of the interior knot `u`, if the resulting curve
+    /// remains within `tolerance` of this curve.
+    ///
+    /// Returns the new curve and an upper bound on its distance from this
+    /// curve, or `None` if the knot cannot be removed.
+    fn remove_knot_once(&self, u: N, tolerance: N) -> Option<(Self, N)> {
+        let p = self.degree;
+        let s = self.knots.multiplicity(u);
+        let r = self.knots.find_span(u);
+        let first = r - p;
+        let last = r - s;
+        let off = first - 1;
+        let pw = self.homogeneous_control_points();
+
+        // compute new control points from the left and the right
+        let mut temp = vec![pw[off].clone(); last - off + 2];
+        temp[last + 1 - off] = pw[last + 1].clone();
+        let (mut i, mut j) = (first, last);
+        let (mut ii, mut jj) = (1, last - off);
+        while j > i {
+            let alfi = (u - self.knots[i]) / (self.knots[i + p + 1] - self.knots[i]);
+            let alfj = (u - self.knots[j]) / (self.knots[j + p + 1] - self.knots[j]);
+            temp[ii] =
+                (pw[i].clone() - temp[ii - 1].clone() * (N::one() - alfi)) * (N::one() / alfi);
+            temp[jj] =
+                (pw[j].clone() - temp[jj + 1].clone() * alfj) * (N::one() / (N::one() - alfj));
+            i += 1;
+            ii += 1;
+            j -= 1;
+            jj -= 1;
+        }
+
+        // the difference between the left and right solutions where they meet
+        // determines whether the knot is removable
+        let difference = if j < i {
+            temp[ii - 1].clone() - temp[jj + 1].clone()
+        } else {
+            let alfi = (u - self.knots[i]) / (self.knots[i + p + 1] - self.knots[i]);
+            pw[i].clone() - temp[ii - 1].lerp(&temp[ii + 1], alfi)
+        };
+
+        // save the new control points
+        let mut qw = pw;
+        let (mut i, mut j) = (first, last);
+        while j > i {
+            qw[i] = temp[i - off].clone();
+            qw[j] = temp[j - off].clone();
+            i += 1;
+            j -= 1;
+        }
+        qw.remove((2 * r - s - p) / 2);
+        if qw.iter().any(|q| q.weight <= N::zero()) {
+            return None;
+        }
+
+        // convert the homogeneous difference into a bound on the Cartesian
+        // distance between the old and new curves, which does not depend on
+        // where the curve lies relative to the origin
+        let deviation = self.deviation_bound(&[difference], &qw);
+
+        if deviation <= tolerance {
+            let knots = self.knots.remove(u, 1).expect("removing an interior knot");
+            Some((Curve::from_homogeneous(p, qw, knots), deviation))
+        } else {
+            None
+        }
     }
 
-    /// The correct number of knot values must be supplied.
-    #[test]
-    fn invalid_knot_count() {
-        let result = TC::new(
-            1,
-            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
-            vec![1.0, 1.0],
-            KnotVec::new(vec![0.0, 1.0]).unwrap(),
-        );
-        assert_eq!(
-            result,
-            Err(CurveError::InvalidKnotCount {
-                required_knot_len: 4,
-                receieved_knot_len: 2
-            })
-        );
+    /// Elevates the degree of the curve.
+    ///
+    /// Returns a new curve of degree `degree + t` which is exactly equivalent
+    /// to this one (it has the same shape and the same parameterization).
+    /// The multiplicity of each interior knot is increased by `t`, so that
+    /// the continuity of the curve at each knot is unchanged.
+    ///
+    /// The curve is first decomposed into Bézier segments, the degree of each
+    /// segment is elevated, and then the redundant interior knots are removed
+    /// again. The returned curve is clamped.
+    ///
+    /// # Parameters
+    ///
+    /// * `t` - the amount by which to increase the degree
+    pub fn elevate_degree(&self, t: usize) -> Self {
+        if t == 0 {
+            return self.clone();
+        }
+        let p = self.degree;
+        let interior_knots = self.interior_knots();
+
+        // elevate the degree of each Bézier segment, and assemble them into a
+        // curve which has full-multiplicity interior knots
+        let segments: Vec<Vec<Homogeneous<N, V>>> = self
+            .bezier_decomposition()
+            .iter()
+            .map(|segment| elevate_bezier(segment, t))
+            .collect();
+        let joins: Vec<(N, bool)> = interior_knots.iter().map(|&(u, s)| (u, s > p)).collect();
+        let mut curve =
+            Curve::from_bezier_segments(p + t, self.min_u(), self.max_u(), &joins, segments);
+
+        // remove the interior knots which were added by the decomposition;
+        // these knots are removable exactly, so no tolerance is required
+        for &(u, s) in &interior_knots {
+            for _ in s..p {
+                curve = curve
+                    .remove_knot_once(u, N::max_value())
+                    .expect("knots added during Bézier decomposition must be removable")
+                    .0;
+            }
+        }
+
+        curve
     }
 
-    /// Test that we detect a non-clamped knot vector.
-    #[test]
-    fn knot_vector_not_clamped() {
-        let result = TC::new(
-            2,
-            vec![
-                Vector2::new(0.0, 0.0),
-                Vector2::new(1.0, 2.0),
-                Vector2::new(3.0, 4.0),
-            ],
-            vec![1.0, 1.0, 1.0],
-            KnotVec::new(vec![0.0, 0.0, 0.5, 0.5, 0.9, 1.0]).unwrap(),
-        );
-        assert_eq!(result, Err(CurveError::KnotVectorNotClamped));
+    /// Reduces the degree of the curve by one, if possible within a tolerance.
+    ///
+    /// In general, a curve cannot be represented exactly by a curve of lower
+    /// degree, so degree reduction is approximate. The curve is decomposed
+    /// into Bézier segments, and the degree of each segment is reduced
+    /// separately (following section 5.6 of The NURBS Book). The segments are
+    /// then joined into a single curve, and as many of the interior knots as
+    /// the tolerance allows are removed again. The returned curve is clamped.
+    ///
+    /// Returns the reduced curve along with an upper bound on its distance
+    /// from this curve (measured between points at the same parameter value).
+    /// To reduce the degree by more than one, this method can be called
+    /// repeatedly, with the tolerance reduced by the deviation of each
+    /// previous step, as in the example below.
+    ///
+    /// An error is returned if the degree of the curve is 1, or if the degree
+    /// cannot be reduced within the tolerance.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the maximum allowed distance from the original curve
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::Curve;
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::Vector2;
+    ///
+    /// let cubic = Curve::new(
+    ///     3,
+    ///     vec![
+    ///         Vector2::new(0.0f64, 0.0),
+    ///         Vector2::new(1.0, 2.0),
+    ///         Vector2::new(3.0, 2.0),
+    ///         Vector2::new(4.0, 0.0),
+    ///     ],
+    ///     vec![1.0; 4],
+    ///     KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// // reduce a degree 7 curve to a cubic, one degree at a time
+    /// let tolerance = 1e-9;
+    /// let mut curve = cubic.elevate_degree(4);
+    /// let mut deviation = 0.0;
+    /// while curve.degree() > 3 {
+    ///     let (reduced, step) = curve.reduce_degree(tolerance - deviation).unwrap();
+    ///     curve = reduced;
+    ///     deviation += step;
+    /// }
+    /// assert!(deviation <= tolerance);
+    /// ```
+    pub fn reduce_degree(&self, tolerance: N) -> Result<(Self, N)> {
+        if self.degree == 1 {
+            return Err(CurveError::InvalidDegree);
+        }
+        let p = self.degree;
+        let interior_knots = self.interior_knots();
+
+        // reduce the degree of each Bézier segment, keeping track of the
+        // largest deviation of any segment
+        let mut segments = Vec::with_capacity(interior_knots.len() + 1);
+        let mut deviation = N::zero();
+        for segment in self.bezier_decomposition() {
+            let reduced = reduce_bezier(&segment);
+            if reduced.iter().any(|q| q.weight <= N::zero()) {
+                return Err(CurveError::DegreeReductionToleranceExceeded);
+            }
+
+            // the reduced segment is compared with the original by elevating
+            // it back to the original degree; the differences between the
+            // control points bound the difference between the curves
+            let differences: Vec<Homogeneous<N, V>> = elevate_bezier(&reduced, 1)
+                .into_iter()
+                .zip(segment)
+                .map(|(a, b)| a - b)
+                .collect();
+            deviation = deviation.max(self.deviation_bound(&differences, &reduced));
+            segments.push(reduced);
+        }
+        if deviation > tolerance {
+            return Err(CurveError::DegreeReductionToleranceExceeded);
+        }
+        let joins: Vec<(N, bool)> = interior_knots.iter().map(|&(u, s)| (u, s > p)).collect();
+        let mut curve =
+            Curve::from_bezier_segments(p - 1, self.min_u(), self.max_u(), &joins, segments);
+
+        // restore the continuity of the original curve by removing knots
+        for &(u, s) in &interior_knots {
+            let target_multiplicity = if s > 1 { s - 1 } else { 1 };
+            while curve.knots.multiplicity(u) > target_multiplicity {
+                match curve.remove_knot_once(u, tolerance - deviation) {
+                    Some((c, e)) => {
+                        curve = c;
+                        deviation += e;
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        Ok((curve, deviation))
     }
 
-    /// Creating a new NURBS curve successfully.
-    #[test]
-    fn new() {
-        let nurbs = TC::new(
-            1,
-            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
-            vec![1.0, 1.0],
-            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
-        )
-        .unwrap();
-        assert_eq!(nurbs.knots().min_u(), 0.0);
-        assert_eq!(nurbs.knots().max_u(), 1.0);
-        assert_eq!(
-            nurbs.control_points(),
-            &vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)]
-        );
+    /// Decomposes the curve into Bézier segments.
+    ///
+    /// Returns one curve for each non-empty knot span of this curve. Each
+    /// segment is a (rational) Bézier curve: it has the same degree as this
+    /// curve, `degree + 1` control points, and a knot vector containing only
+    /// its start and end parameter values, each repeated `degree + 1` times.
+    /// The parameter range of each segment (available through its `knots`)
+    /// is the range of the corresponding span of this curve, and the segment
+    /// matches this curve exactly over that range.
+    ///
+    /// The segments are returned in order, and each one starts at the end
+    /// point of the previous one, unless the curve is broken between them
+    /// by a knot whose multiplicity exceeds the degree.
+    pub fn to_bezier_segments(&self) -> Vec<Self> {
+        let breakpoints = self.breakpoints();
+        let p = self.degree;
+        self.bezier_decomposition()
+            .into_iter()
+            .zip(breakpoints.windows(2))
+            .map(|(points, range)| {
+                let mut knots = vec![range[0]; p + 1];
+                knots.extend(std::iter::repeat_n(range[1], p + 1));
+                Curve::from_homogeneous(
+                    p,
+                    points,
+                    KnotVec::new(knots).expect("Bézier segment knots must be valid"),
+                )
+            })
+            .collect()
     }
 
-    /// Uniformly scaling a NURBS curve.
-    #[test]
-    fn uniform_scale() {
-        let mut nurbs = TC::new(
-            1,
-            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
-            vec![1.0, 1.0],
-            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
-        )
-        .unwrap();
-        nurbs.uniform_scale(2.0);
+    /// Splits the curve into two curves at a parameter value.
+    ///
+    /// Returns a pair of clamped curves: the first matches this curve
+    /// exactly over the parameter range `min_u..=u`, and the second matches
+    /// it over the range `u..=max_u`. The curves keep the parameterization of
+    /// this curve. The split is performed by inserting the knot `u` until its
+    /// multiplicity equals the degree of the curve.
+    ///
+    /// The parameter `u` must lie strictly inside the parameter range of the
+    /// curve, so that both curves are non-degenerate.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the parameter value at which to split the curve
+    pub fn split_at(&self, u: N) -> Result<(Self, Self)> {
+        if u <= self.min_u() || u >= self.max_u() {
+            return Err(CurveError::ParameterOutOfRange);
+        }
+        let p = self.degree;
+        let curve = self.clamp();
+        let s = curve.knots.multiplicity(u);
+        let curve = if s < p {
+            curve.insert_knot(u, p - s)?
+        } else {
+            curve
+        };
 
-        let expected = TC::new(
-            1,
-            vec![Vector2::new(0.0, 0.0), Vector2::new(2.0 * 42.0, 2.0 * 56.0)],
-            vec![1.0, 1.0],
-            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
-        )
-        .unwrap();
+        // the first of the m copies of u in the knot vector, where m is p
+        // unless the curve is already broken at u
+        let m = s.max(p);
+        let a = curve.knots.find_span(u) + 1 - m;
+        let knots = curve.knots.as_slice();
 
-        assert_eq!(nurbs, expected);
+        let mut left_knots = knots[..a].to_vec();
+        left_knots.extend(std::iter::repeat_n(u, p + 1));
+        let left = Curve {
+            degree: p,
+            control_points: curve.control_points[..a].to_vec(),
+            weights: curve.weights[..a].to_vec(),
+            knots: KnotVec::new(left_knots).expect("split knot vector must be valid"),
+        };
+
+        let mut right_knots = vec![u; p + 1];
+        right_knots.extend_from_slice(&knots[a + m..]);
+        let b = a + m - p - 1;
+        let right = Curve {
+            degree: p,
+            control_points: curve.control_points[b..].to_vec(),
+            weights: curve.weights[b..].to_vec(),
+            knots: KnotVec::new(right_knots).expect("split knot vector must be valid"),
+        };
+
+        Ok((left, right))
     }
 
-    /// Test de Boor evalutaion on a non-rational, uniform Bezier.
-    #[test]
-    fn de_boor_non_rational_uniform_bezier() {
-        let test_curve = TC::new(
-            3,
-            vec![
-                Vector2::new(-10.0, 10.0),
-                Vector2::new(10.0, 10.0),
-                Vector2::new(-10.0, -10.0),
-                Vector2::new(10.0, -10.0),
+    /// Extracts the part of the curve between two parameter values.
+    ///
+    /// Returns a clamped curve which matches this curve exactly over the
+    /// parameter range `u0..=u1`, and which keeps the parameterization of this
+    /// curve.
+    ///
+    /// # Parameters
+    ///
+    /// * `u0` - the parameter value at the start of the sub-curve
+    /// * `u1` - the parameter value at the end of the sub-curve (must be
+    ///   greater than `u0`)
+    pub fn subcurve(&self, u0: N, u1: N) -> Result<Self> {
+        if u0 >= u1 {
+            return Err(CurveError::DegenerateParameterRange);
+        }
+        if u0 < self.min_u() || u1 > self.max_u() {
+            return Err(CurveError::ParameterOutOfRange);
+        }
+        let curve = if u0 > self.min_u() {
+            self.split_at(u0)?.1
+        } else {
+            self.clamp()
+        };
+        if u1 < curve.max_u() {
+            Ok(curve.split_at(u1)?.0)
+        } else {
+            Ok(curve)
+        }
+    }
+
+    /// Reverses the direction of the curve.
+    ///
+    /// Returns a new curve with the same shape, which is traversed in the
+    /// opposite direction over the same parameter range. If this curve is
+    /// `C(u)`, the reversed curve is `C(min_u + max_u - u)`.
+    pub fn reversed(&self) -> Self {
+        let (min_u, max_u) = (self.min_u(), self.max_u());
+        let knots = self
+            .knots
+            .as_slice()
+            .iter()
+            .rev()
+            .map(|&u| min_u + max_u - u)
+            .collect();
+        Curve {
+            degree: self.degree,
+            control_points: self.control_points.iter().rev().cloned().collect(),
+            weights: self.weights.iter().rev().copied().collect(),
+            knots: KnotVec::new(knots).expect("reversed knot vector must be valid"),
+        }
+    }
+
+    /// Changes the parameter range of the curve using an affine map.
+    ///
+    /// Returns a new curve with the same shape, whose parameter range is
+    /// `new_min..=new_max`. The knots are mapped linearly from the current
+    /// parameter range to the new range, so the relative spacing of the
+    /// knots (and the speed of the curve, up to a constant factor) is
+    /// unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `new_min` - the new minimum parameter value
+    /// * `new_max` - the new maximum parameter value (must be greater than
+    ///   `new_min`)
+    pub fn reparameterize(&self, new_min: N, new_max: N) -> Result<Self> {
+        if new_min >= new_max {
+            return Err(CurveError::DegenerateParameterRange);
+        }
+        let (min_u, max_u) = (self.min_u(), self.max_u());
+        let scale = (new_max - new_min) / (max_u - min_u);
+        let knots = self
+            .knots
+            .as_slice()
+            .iter()
+            .map(|&u| {
+                // the ends are mapped exactly, to avoid rounding errors
+                if u == max_u {
+                    new_max
+                } else {
+                    new_min + (u - min_u) * scale
+                }
+            })
+            .collect();
+        Ok(Curve {
+            knots: KnotVec::new(knots).expect("reparameterized knot vector must be valid"),
+            ..self.clone()
+        })
+    }
+
+    /// Changes the parameterization of the curve using a Möbius
+    /// (rational linear) map, without changing its shape.
+    ///
+    /// The parameter range of the curve is unchanged, but points move along
+    /// the curve. Writing `t` and `s` for the old and new parameters, each
+    /// normalized to the range `0..=1`, the new curve `D(s)` satisfies
+    /// `D(s) = C(t)` where
+    ///
+    /// ```text
+    /// t = s / (s + c (1 - s))
+    /// ```
+    ///
+    /// A factor `c > 1` moves points towards the start of the parameter range
+    /// (the start of the curve is traversed more slowly), while `c < 1` moves
+    /// them towards the end. A factor of `1` leaves the curve unchanged. The
+    /// knots are mapped by the inverse of this function, and the weights are
+    /// modified so that the curve remains exactly the same shape (following
+    /// Lee and Lucian, "Möbius reparametrizations of rational B-splines",
+    /// 1991).
+    ///
+    /// The map is only defined over the parameter range of the curve, so an
+    /// unclamped or periodic curve is clamped first, and the returned curve
+    /// is always clamped.
+    ///
+    /// # Parameters
+    ///
+    /// * `c` - the Möbius factor (must be greater than zero)
+    pub fn mobius_reparameterize(&self, c: N) -> Result<Self> {
+        if c <= N::zero() {
+            return Err(CurveError::InvalidMobiusFactor);
+        }
+        // the map is only defined over the parameter range of the curve, so
+        // any knots outside it are removed first
+        let curve = self.clamp();
+        let (min_u, max_u) = (curve.min_u(), curve.max_u());
+        let range = max_u - min_u;
+
+        // normalized new knots, and the denominator of the map at each knot
+        let s: Vec<N> = curve
+            .knots
+            .as_slice()
+            .iter()
+            .map(|&u| {
+                let t = (u - min_u) / range;
+                c * t / (N::one() - t + c * t)
+            })
+            .collect();
+        let denominator: Vec<N> = s.iter().map(|&s| s + c * (N::one() - s)).collect();
+
+        let p = curve.degree;
+        let weights = curve
+            .weights
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| {
+                denominator[i + 1..i + p + 1]
+                    .iter()
+                    .fold(w, |acc, &d| acc * d)
+            })
+            .collect();
+        let knots = s
+            .iter()
+            .map(|&s| {
+                if s == N::one() {
+                    max_u
+                } else {
+                    min_u + s * range
+                }
+            })
+            .collect();
+
+        Ok(Curve {
+            degree: p,
+            control_points: curve.control_points,
+            weights,
+            knots: KnotVec::new(knots).expect("reparameterized knot vector must be valid"),
+        })
+    }
+
+    /// Converts the curve to a clamped curve.
+    ///
+    /// Returns a clamped curve which is identical to this curve over its
+    /// parameter range. Knots are inserted at either end of the parameter
+    /// range until their multiplicity equals the degree, and the knots and
+    /// control points outside the parameter range are then discarded. A
+    /// clamped curve is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::{Curve, CurveKind};
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::Vector2;
+    ///
+    /// let periodic = Curve::new(
+    ///     1,
+    ///     vec![
+    ///         Vector2::new(0.0, 0.0),
+    ///         Vector2::new(1.0, 0.0),
+    ///         Vector2::new(0.0, 1.0),
+    ///         Vector2::new(0.0, 0.0),
+    ///     ],
+    ///     vec![1.0; 4],
+    ///     KnotVec::new(vec![-1.0, 0.0, 1.0, 2.0, 3.0, 4.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(periodic.kind(), CurveKind::Periodic);
+    ///
+    /// let clamped = periodic.clamp();
+    /// assert_eq!(clamped.kind(), CurveKind::Clamped);
+    /// assert_eq!(clamped.knots().as_slice(), &[0.0, 0.0, 1.0, 2.0, 3.0, 3.0]);
+    /// assert_eq!(clamped.de_boor(1.5), periodic.de_boor(1.5));
+    /// ```
+    pub fn clamp(&self) -> Self {
+        let p = self.degree;
+        if self.knots.is_clamped(p) {
+            return self.clone();
+        }
+        let (a, b) = (self.min_u(), self.max_u());
+        let mut new_knots = vec![a; p.saturating_sub(self.knots.multiplicity(a))];
+        new_knots.extend(std::iter::repeat_n(
+            b,
+            p.saturating_sub(self.knots.multiplicity(b)),
+        ));
+        let curve = self
+            .refine_knots(&new_knots)
+            .expect("knots at the ends of the parameter range must be valid");
+
+        // the curve interpolates the control points before the first and
+        // after the last copies of the end knots
+        let knots = curve.knots.as_slice();
+        let start = knots.iter().rposition(|&u| u == a).unwrap() - p;
+        let end = knots.iter().position(|&u| u == b).unwrap();
+        let mut clamped_knots = vec![a; p + 1];
+        clamped_knots.extend_from_slice(&knots[start + p + 1..end]);
+        clamped_knots.extend(std::iter::repeat_n(b, p + 1));
+        Curve {
+            degree: p,
+            control_points: curve.control_points[start..end].to_vec(),
+            weights: curve.weights[start..end].to_vec(),
+            knots: KnotVec::new(clamped_knots).expect("clamped knot vector must be valid"),
+        }
+    }
+
+    /// Converts the curve to an unclamped curve.
+    ///
+    /// Returns an unclamped curve which is identical to this curve over its
+    /// parameter range. The curve is first clamped, and the knots at either
+    /// end are then replaced by knots whose spacing repeats the spacing of
+    /// the knots at the opposite end of the parameter range, modifying the
+    /// first and last `degree - 1` control points to match (algorithm A12.1
+    /// from The NURBS Book).
+    ///
+    /// If the curve is closed, and its derivatives up to order `degree - 1`
+    /// are continuous where its ends meet, then the result is a periodic
+    /// curve (see `CurveKind::Periodic`).
+    ///
+    /// An error is returned if any of the modified control points would have
+    /// a weight which is not positive. This can only happen for rational
+    /// curves.
+    pub fn unclamp(&self) -> Result<Self> {
+        let curve = self.clamp();
+        let p = curve.degree;
+        let n = curve.control_points.len() - 1;
+        let mut u = curve.knots.as_slice().to_vec();
+        let mut pw = curve.homogeneous_control_points();
+
+        // unclamp the start of the curve
+        for i in 0..p - 1 {
+            u[p - i - 1] = u[p - i] - (u[n - i + 1] - u[n - i]);
+            for j in (0..i + 1).rev() {
+                let k = p - 1 - i + j;
+                let alpha = (u[p] - u[k]) / (u[p + j + 1] - u[k]);
+                pw[j] =
+                    (pw[j].clone() - pw[j + 1].clone() * alpha) * (N::one() / (N::one() - alpha));
+            }
+        }
+        u[0] = u[1] - (u[n - p + 2] - u[n - p + 1]);
+
+        // unclamp the end of the curve
+        for i in 0..p - 1 {
+            u[n + i + 2] = u[n + i + 1] + (u[p + i + 1] - u[p + i]);
+            for j in (0..i + 1).rev() {
+                let alpha = (u[n + 1] - u[n - j]) / (u[n - j + i + 2] - u[n - j]);
+                pw[n - j] = (pw[n - j].clone() - pw[n - j - 1].clone() * (N::one() - alpha))
+                    * (N::one() / alpha);
+            }
+        }
+        u[n + p + 1] = u[n + p] + (u[2 * p] - u[2 * p - 1]);
+
+        if pw.iter().any(|q| q.weight <= N::zero()) {
+            return Err(CurveError::UnclampedWeightNotPositive);
+        }
+        Ok(Curve::from_homogeneous(
+            p,
+            pw,
+            KnotVec::new(u).expect("unclamped knot vector must be valid"),
+        ))
+    }
+
+    /// Returns the kind of knot vector of the curve.
+    ///
+    /// A curve is recognized as periodic if its control points, weights and
+    /// knot spacings repeat to within rounding error (a relative tolerance
+    /// of the square root of machine epsilon).
+    pub fn kind(&self) -> CurveKind {
+        if self.knots.is_clamped(self.degree) {
+            CurveKind::Clamped
+        } else if self.is_periodic() {
+            CurveKind::Periodic
+        } else {
+            CurveKind::Unclamped
+        }
+    }
+
+    /// Returns the minimum parameter value of the curve.
+    ///
+    /// This is the knot `knots[degree]`, which is the first knot of a clamped
+    /// curve.
+    pub fn min_u(&self) -> N {
+        self.knots[self.degree]
+    }
+
+    /// Returns the maximum parameter value of the curve.
+    ///
+    /// This is the knot `knots[control_points.len()]`, which is the last knot
+    /// of a clamped curve.
+    pub fn max_u(&self) -> N {
+        self.knots[self.control_points.len()]
+    }
+
+    /// Returns the degree of the curve.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the vector of control points.
+    pub fn control_points(&self) -> &Vec<V> {
+        &self.control_points
+    }
+
+    /// Returns the vector of weights.
+    pub fn weights(&self) -> &Vec<N> {
+        &self.weights
+    }
+
+    /// Returns the knot vector.
+    pub fn knots(&self) -> &KnotVec<N> {
+        &self.knots
+    }
+
+    /// Clamps a parameter value to the parameter range of the curve.
+    fn clamp_param(&self, u: N) -> N {
+        u.max(self.min_u()).min(self.max_u())
+    }
+
+    /// Finds the knot span containing the parameter value `u`, which must be
+    /// within the parameter range of the curve.
+    ///
+    /// This is the same as `KnotVec::find_span`, except that at `max_u` the
+    /// last non-empty span within the parameter range of the curve is
+    /// returned, even if the curve is unclamped.
+    fn find_span(&self, u: N) -> usize {
+        if u >= self.max_u() {
+            (self.degree..self.control_points.len())
+                .rev()
+                .find(|&k| self.knots[k] < u)
+                .expect("the parameter range of a curve must not be empty")
+        } else {
+            self.knots.find_span(u)
+        }
+    }
+
+    /// Checks whether the control points, weights and knot spacings of the
+    /// curve repeat periodically.
+    fn is_periodic(&self) -> bool {
+        let p = self.degree;
+        let period = self.control_points.len() - p;
+        let tolerance = N::default_epsilon().sqrt();
+
+        let knots = self.knots.as_slice();
+        let knot_tolerance = tolerance * (self.max_u() - self.min_u());
+        let knots_repeat = (0..2 * p).all(|i| {
+            let a = knots[i + 1] - knots[i];
+            let b = knots[i + period + 1] - knots[i + period];
+            (a - b).abs() <= knot_tolerance
+        });
+
+        let point_tolerance = tolerance * self.control_bounding_box().diagonal();
+        let weight_tolerance = tolerance * self.weights.iter().fold(N::zero(), |a, &b| a.max(b));
+        let points_repeat = (0..p).all(|i| {
+            let (a, b) = (i, i + period);
+            (self.control_points[a].clone() - self.control_points[b].clone()).norm()
+                <= point_tolerance
+                && (self.weights[a] - self.weights[b]).abs() <= weight_tolerance
+        });
+
+        knots_repeat && points_repeat
+    }
+
+    /// Returns the control points of the curve in homogeneous coordinates.
+    fn homogeneous_control_points(&self) -> Vec<Homogeneous<N, V>> {
+        self.control_points
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(point, &weight)| Homogeneous::from_cartesian(point, weight))
+            .collect()
+    }
+
+    /// Computes an upper bound on the distance between this curve and a
+    /// modified curve, given the differences between their homogeneous
+    /// control points.
+    ///
+    /// If the homogeneous control points change by the `differences`, then
+    /// the homogeneous curve changes by at most the largest difference. For a
+    /// rational curve, the Cartesian change also depends on the change in
+    /// weight. Measuring points relative to the centroid `c` of the control
+    /// points, the change is bounded by
+    /// `(max|dP - c dw| + max|P - c| max|dw|) / min(w')`, where `w'` are the
+    /// weights of the modified curve (`new_points`).
+    fn deviation_bound(
+        &self,
+        differences: &[Homogeneous<N, V>],
+        new_points: &[Homogeneous<N, V>],
+    ) -> N {
+        let n = N::from_usize(self.control_points.len()).unwrap();
+        let centroid = self
+            .control_points
+            .iter()
+            .fold(V::zero(), |a, b| a + b.clone())
+            * (N::one() / n);
+        let radius = self
+            .control_points
+            .iter()
+            .map(|cp| (cp.clone() - centroid.clone()).norm())
+            .fold(N::zero(), |a, b| a.max(b));
+        let (max_point_diff, max_weight_diff) = differences
+            .iter()
+            .map(|d| {
+                (
+                    (d.point.clone() - centroid.clone() * d.weight).norm(),
+                    d.weight.abs(),
+                )
+            })
+            .fold((N::zero(), N::zero()), |(a, b), (c, d)| {
+                (a.max(c), b.max(d))
+            });
+        let min_weight = new_points
+            .iter()
+            .map(|q| q.weight)
+            .fold(new_points[0].weight, |a, b| a.min(b));
+        (max_point_diff + radius * max_weight_diff) / min_weight
+    }
+
+    /// Returns the distinct interior knots of the curve, along with their
+    /// multiplicities.
+    fn interior_knots(&self) -> Vec<(N, usize)> {
+        let (min_u, max_u) = (self.min_u(), self.max_u());
+        let mut interior_knots: Vec<(N, usize)> = Vec::new();
+        for &u in self.knots.as_slice() {
+            if u > min_u && u < max_u {
+                match interior_knots.last_mut() {
+                    Some((v, s)) if *v == u => *s += 1,
+                    _ => interior_knots.push((u, 1)),
+                }
+            }
+        }
+        interior_knots
+    }
+
+    /// Returns the distinct knot values of the curve, from `min_u` to
+    /// `max_u`.
+    ///
+    /// These are the boundaries of the non-empty knot spans, across which
+    /// the curve may not be smooth.
+    fn breakpoints(&self) -> Vec<N> {
+        let mut breakpoints = vec![self.min_u()];
+        breakpoints.extend(self.interior_knots().into_iter().map(|(u, _)| u));
+        breakpoints.push(self.max_u());
+        breakpoints
+    }
+
+    /// Decomposes the curve into Bézier segments.
+    ///
+    /// Returns the homogeneous control points of each Bézier segment, in
+    /// order. Each segment has `degree + 1` control points, and the segments
+    /// are separated by the distinct interior knots of the curve. Knots are
+    /// inserted until every interior knot has a multiplicity of at least
+    /// `degree`; where a knot already has a greater multiplicity, the curve
+    /// is broken, and the segments on either side do not share an end point.
+    fn bezier_decomposition(&self) -> Vec<Vec<Homogeneous<N, V>>> {
+        let p = self.degree;
+        let mut new_knots = Vec::new();
+        for (u, s) in self.interior_knots() {
+            new_knots.extend(std::iter::repeat_n(u, p.saturating_sub(s)));
+        }
+        let decomposed = self
+            .clamp()
+            .refine_knots(&new_knots)
+            .expect("Bézier decomposition knots must be valid");
+        let pw = decomposed.homogeneous_control_points();
+
+        // each segment is controlled by the p + 1 control points ending at
+        // the span which starts with the last copy of its first knot
+        let breakpoints = self.breakpoints();
+        breakpoints[..breakpoints.len() - 1]
+            .iter()
+            .map(|&u| {
+                let span = decomposed.find_span(u);
+                pw[span - p..=span].to_vec()
+            })
+            .collect()
+    }
+
+    /// Creates a curve from Bézier segments which join end-to-end.
+    ///
+    /// The resulting curve has interior knots of multiplicity `degree`
+    /// between the segments, or `degree + 1` where the curve is broken.
+    ///
+    /// # Parameters
+    ///
+    /// * `degree` - degree of the segments
+    /// * `min_u` - the parameter value at the start of the curve
+    /// * `max_u` - the parameter value at the end of the curve
+    /// * `joins` - the parameter value between each pair of segments, and
+    ///   whether the curve is broken there (in which case the segments need
+    ///   not meet, and the knot has multiplicity `degree + 1`)
+    /// * `segments` - homogeneous control points of each segment
+    fn from_bezier_segments(
+        degree: usize,
+        min_u: N,
+        max_u: N,
+        joins: &[(N, bool)],
+        segments: Vec<Vec<Homogeneous<N, V>>>,
+    ) -> Self {
+        debug_assert_eq!(segments.len(), joins.len() + 1);
+        let mut points = Vec::with_capacity(segments.len() * (degree + 1));
+        for (i, segment) in segments.into_iter().enumerate() {
+            debug_assert_eq!(segment.len(), degree + 1);
+            // segments which meet share their end points
+            let skip = if i == 0 || joins[i - 1].1 { 0 } else { 1 };
+            points.extend(segment.into_iter().skip(skip));
+        }
+        let mut knots = vec![min_u; degree + 1];
+        for &(u, broken) in joins {
+            let multiplicity = if broken { degree + 1 } else { degree };
+            knots.extend(std::iter::repeat_n(u, multiplicity));
+        }
+        knots.extend(std::iter::repeat_n(max_u, degree + 1));
+        Curve::from_homogeneous(
+            degree,
+            points,
+            KnotVec::new(knots).expect("Bézier segment knots must be valid"),
+        )
+    }
+
+    /// Creates a curve from control points in homogeneous coordinates.
+    ///
+    /// This is used internally by algorithms which are guaranteed to produce
+    /// valid curves, so the properties checked by `Curve::new` are only
+    /// checked in debug builds.
+    fn from_homogeneous(degree: usize, points: Vec<Homogeneous<N, V>>, knots: KnotVec<N>) -> Self {
+        debug_assert!(points.len() > degree);
+        debug_assert_eq!(knots.len(), degree + points.len() + 1);
+        debug_assert!(knots[degree] < knots[points.len()]);
+        let (control_points, weights) = points.iter().map(|h| (h.to_cartesian(), h.weight)).unzip();
+        Curve {
+            degree,
+            control_points,
+            weights,
+            knots,
+        }
+    }
+}
+
+impl<N, D> Curve<N, VectorN<N, D>>
+where
+    N: 'static + ScalarT,
+    D: DimName + DimNameAdd<U1>,
+    DefaultAllocator: Allocator<N, D>
+        + Allocator<N, DimNameSum<D, U1>>
+        + Allocator<N, DimNameSum<D, U1>, DimNameSum<D, U1>>,
+{
+    /// Transforms the curve by a homogeneous transformation matrix.
+    ///
+    /// The matrix may represent any affine or projective transformation,
+    /// such as those produced by `to_homogeneous()` on an `nalgebra`
+    /// `Isometry` or `Similarity`, or by `matrix()` on an `Affine` or
+    /// `Projective` transform. NURBS curves are invariant under projective
+    /// transformations: the transformation is applied to the homogeneous
+    /// control points, so the weights are updated as required and the
+    /// transformed curve is exactly the image of the original curve. For
+    /// example, the perspective projection of a circle remains an exact
+    /// conic.
+    ///
+    /// A projective transformation must not map any control point to
+    /// infinity or behind the viewer; all of the transformed weights must
+    /// have the same sign. If this is not the case, an error is returned and
+    /// the curve is left unchanged.
+    ///
+    /// Note that a homogeneous matrix which scales every component, such as
+    /// `Matrix3::new_scaling(s)`, leaves points unchanged. Use a
+    /// `Similarity` or a non-uniform scaling matrix to scale a curve.
+    ///
+    /// # Parameters
+    ///
+    /// * `matrix` - the homogeneous transformation matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::Curve;
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::{Similarity2, Vector2};
+    ///
+    /// let mut line = Curve::new(
+    ///     1,
+    ///     vec![Vector2::new(0.0, 0.0), Vector2::new(1.0, 2.0)],
+    ///     vec![1.0, 1.0],
+    ///     KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let scaling = Similarity2::from_scaling(2.0);
+    /// line.transform(&scaling.to_homogeneous()).unwrap();
+    /// assert_eq!(line.de_boor(1.0), Vector2::new(2.0, 4.0));
+    /// ```
+    pub fn transform(&mut self, matrix: &MatrixN<N, DimNameSum<D, U1>>) -> Result<()> {
+        let transformed: Vec<_> = self
+            .control_points
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(cp, &w)| matrix * (Point::from(cp.clone()).to_homogeneous() * w))
+            .collect();
+
+        // the homogeneous coordinates of each control point can be scaled by
+        // -1 without changing the curve, so weights that are all negative are
+        // acceptable
+        let sign = if transformed[0][D::dim()] < N::zero() {
+            -N::one()
+        } else {
+            N::one()
+        };
+        if transformed.iter().any(|h| h[D::dim()] * sign <= N::zero()) {
+            return Err(CurveError::TransformedWeightNotPositive);
+        }
+
+        for (i, h) in transformed.into_iter().enumerate() {
+            self.weights[i] = h[D::dim()] * sign;
+            self.control_points[i] = Point::from_homogeneous(h)
+                .expect("transformed weights must be non-zero")
+                .coords;
+        }
+        Ok(())
+    }
+}
+
+/// A control point in homogeneous coordinates.
+///
+/// The `point` is the Cartesian control point multiplied by its `weight`.
+/// Algorithms which modify the control points of a rational curve (knot
+/// insertion, degree elevation, etc.) operate in homogeneous coordinates, where
+/// the curve is a simple, non-rational B-spline.
+#[derive(Clone, Debug)]
+struct Homogeneous<N, V> {
+    point: V,
+    weight: N,
+}
+
+impl<N, V> Homogeneous<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Converts a Cartesian point and its weight to homogeneous coordinates.
+    fn from_cartesian(point: &V, weight: N) -> Self {
+        Homogeneous {
+            point: point.clone() * weight,
+            weight,
+        }
+    }
+
+    /// Converts the homogeneous point back to Cartesian coordinates.
+    fn to_cartesian(&self) -> V {
+        self.point.clone() * (N::one() / self.weight)
+    }
+
+    /// Linearly interpolates from this point (`alpha == 0`) to `other`
+    /// (`alpha == 1`).
+    fn lerp(&self, other: &Self, alpha: N) -> Self {
+        self.clone() * (N::one() - alpha) + other.clone() * alpha
+    }
+}
+
+impl<N, V> Add for Homogeneous<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Homogeneous {
+            point: self.point + rhs.point,
+            weight: self.weight + rhs.weight,
+        }
+    }
+}
+
+impl<N, V> Sub for Homogeneous<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Homogeneous {
+            point: self.point - rhs.point,
+            weight: self.weight - rhs.weight,
+        }
+    }
+}
+
+impl<N, V> Mul<N> for Homogeneous<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: N) -> Self {
+        Homogeneous {
+            point: self.point * rhs,
+            weight: self.weight * rhs,
+        }
+    }
+}
+
+/// Elevates the degree of a Bézier curve, given its homogeneous control
+/// points.
+///
+/// # Parameters
+///
+/// * `points` - the control points of the Bézier curve
+/// * `t` - the amount by which to increase the degree
+fn elevate_bezier<N, V>(points: &[Homogeneous<N, V>], t: usize) -> Vec<Homogeneous<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let p = points.len() - 1;
+    (0..p + t + 1)
+        .map(|i| {
+            let inv = N::one() / binomial::<N>(p + t, i);
+            (i.saturating_sub(t)..p.min(i) + 1)
+                .map(|j| points[j].clone() * (inv * binomial::<N>(p, j) * binomial::<N>(t, i - j)))
+                .fold(
+                    Homogeneous {
+                        point: V::zero(),
+                        weight: N::zero(),
+                    },
+                    |a, b| a + b,
+                )
+        })
+        .collect()
+}
+
+/// Reduces the degree of a Bézier curve by one, given its homogeneous control
+/// points.
+///
+/// The end points are preserved, and the remaining control points are found
+/// from both ends of the curve, as described in section 5.6 of The NURBS Book.
+fn reduce_bezier<N, V>(points: &[Homogeneous<N, V>]) -> Vec<Homogeneous<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let p = points.len() - 1;
+    let r = (p - 1) / 2;
+    let alpha = |i: usize| N::from_usize(i).unwrap() / N::from_usize(p).unwrap();
+
+    let mut reduced = vec![points[0].clone(); p];
+    reduced[p - 1] = points[p].clone();
+    let left = |reduced: &[Homogeneous<N, V>], i: usize| {
+        (points[i].clone() - reduced[i - 1].clone() * alpha(i)) * (N::one() / (N::one() - alpha(i)))
+    };
+    let right = |reduced: &[Homogeneous<N, V>], i: usize| {
+        (points[i + 1].clone() - reduced[i + 1].clone() * (N::one() - alpha(i + 1)))
+            * (N::one() / alpha(i + 1))
+    };
+    if p.is_multiple_of(2) {
+        for i in 1..r + 1 {
+            reduced[i] = left(&reduced, i);
+        }
+        for i in (r + 1..p - 1).rev() {
+            reduced[i] = right(&reduced, i);
+        }
+    } else {
+        for i in 1..r {
+            reduced[i] = left(&reduced, i);
+        }
+        for i in (r + 1..p - 1).rev() {
+            reduced[i] = right(&reduced, i);
+        }
+        if r > 0 {
+            let half = N::from_f64(0.5).unwrap();
+            reduced[r] = (left(&reduced, r) + right(&reduced, r)) * half;
+        }
+    }
+
+    reduced
+}
+
+/// Computes the binomial coefficient `n` choose `k` as a scalar.
+fn binomial<N: ScalarT>(n: usize, k: usize) -> N {
+    let k = k.min(n - k);
+    let mut result = N::one();
+    for i in 0..k {
+        result = result * N::from_usize(n - i).unwrap() / N::from_usize(i + 1).unwrap();
+    }
+    result
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CurveError {
+    #[error("invalid degree; must satisfy degree > 0")]
+    InvalidDegree,
+
+    #[error("N={} control points were supplied; at least {} are required \
+             for a degree {} curve",
+            .number_supplied,
+            .degree,
+            .degree - 1)]
+    InsufficientControlPoints {
+        degree: usize,
+        number_supplied: usize,
+    },
+
+    #[error("number of weights and control points must be identical")]
+    MismatchedWeightsAndControlPoints,
+
+    #[error("expected {} knot values, but received {}",
+            .required_knot_len,
+            .receieved_knot_len)]
+    InvalidKnotCount {
+        required_knot_len: usize,
+        receieved_knot_len: usize,
+    },
+
+    #[error("parameter value is outside the parameter range of the curve")]
+    ParameterOutOfRange,
+
+    #[error("knot multiplicity {} would exceed the curve degree {}",
+            .multiplicity,
+            .degree)]
+    KnotMultiplicityExceedsDegree { degree: usize, multiplicity: usize },
+
+    #[error("knot values must be sorted in non-decreasing order")]
+    UnsortedKnots,
+
+    #[error("knot value was not found in the knot vector")]
+    KnotNotFound,
+
+    #[error("degree could not be reduced within the requested tolerance")]
+    DegreeReductionToleranceExceeded,
+
+    #[error("parameter range is empty; the start must be less than the end")]
+    DegenerateParameterRange,
+
+    #[error("direction or normal vector has zero length")]
+    DegenerateDirection,
+
+    #[error("arc length is outside the length of the curve")]
+    ArcLengthOutOfRange,
+
+    #[error("invalid Möbius factor; must be greater than zero")]
+    InvalidMobiusFactor,
+
+    #[error(
+        "transformation would produce a control point at infinity or \
+             weights that are not all positive"
+    )]
+    TransformedWeightNotPositive,
+
+    #[error("unclamping would produce weights that are not all positive")]
+    UnclampedWeightNotPositive,
+
+    #[error("successive points must not coincide")]
+    CoincidentPoints,
+
+    #[error("the linear system for the control points could not be solved")]
+    SingularSystem,
+
+    #[error("derivative constraint has an invalid point index or order, or is repeated")]
+    InvalidDerivativeConstraint,
+
+    #[error("N={} points were supplied; at least {} are required",
+            .number_supplied,
+            .required)]
+    InsufficientDataPoints {
+        required: usize,
+        number_supplied: usize,
+    },
+
+    #[error("point weights must be non-negative, with one weight for each point")]
+    InvalidPointWeights,
+
+    #[error("points could not be approximated within the requested tolerance")]
+    ApproximationToleranceExceeded,
+
+    #[error("points must have finite coordinates")]
+    NonFinitePoint,
+
+    #[error("curve degree {} is less than the order {} of the fairing energy",
+            .degree,
+            .order)]
+    FairingDegreeTooLow { degree: usize, order: usize },
+
+    #[error("curve could not be faired within the requested tolerance")]
+    FairingToleranceTooSmall,
+
+    #[error("tolerance must not be negative")]
+    NegativeTolerance,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{Matrix3, Similarity2, Vector2, Vector3};
+    use proptest::prelude::*;
+
+    /// Test Curve
+    type TC = Curve<f32, Vector2<f32>>;
+
+    /// The degree of a NURBS curve must be >= 0.
+    #[test]
+    fn invalid_degree() {
+        let result = TC::new(0, vec![], vec![], KnotVec::new(vec![0.0, 1.0]).unwrap());
+        assert_eq!(result, Err(CurveError::InvalidDegree));
+    }
+
+    /// There must be at least degree + 1 control points.
+    #[test]
+    fn insufficient_control_points() {
+        let result = TC::new(
+            1,
+            vec![Vector2::new(0.0, 0.0)],
+            vec![1.0],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        );
+        assert_eq!(
+            result,
+            Err(CurveError::InsufficientControlPoints {
+                degree: 1,
+                number_supplied: 1
+            })
+        )
+    }
+
+    /// The number of control points and weights must be identical.
+    #[test]
+    fn weights_and_cps_lengths_must_be_equal() {
+        let result = TC::new(
+            1,
+            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
+            vec![1.0],
+            KnotVec::new(vec![0.0, 1.0]).unwrap(),
+        );
+        assert_eq!(result, Err(CurveError::MismatchedWeightsAndControlPoints));
+    }
+
+    /// The correct number of knot values must be supplied.
+    #[test]
+    fn invalid_knot_count() {
+        let result = TC::new(
+            1,
+            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
+            vec![1.0, 1.0],
+            KnotVec::new(vec![0.0, 1.0]).unwrap(),
+        );
+        assert_eq!(
+            result,
+            Err(CurveError::InvalidKnotCount {
+                required_knot_len: 4,
+                receieved_knot_len: 2
+            })
+        );
+    }
+
+    /// Test that we detect a knot vector whose parameter range is empty.
+    #[test]
+    fn empty_parameter_range() {
+        let result = TC::new(
+            2,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 2.0),
+                Vector2::new(3.0, 4.0),
+            ],
+            vec![1.0, 1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 0.5, 0.5, 0.9, 1.0]).unwrap(),
+        );
+        assert_eq!(result, Err(CurveError::DegenerateParameterRange));
+    }
+
+    /// Creating a new NURBS curve successfully.
+    #[test]
+    fn new() {
+        let nurbs = TC::new(
+            1,
+            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
+            vec![1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(nurbs.knots().min_u(), 0.0);
+        assert_eq!(nurbs.knots().max_u(), 1.0);
+        assert_eq!(
+            nurbs.control_points(),
+            &vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)]
+        );
+    }
+
+    /// Uniformly scaling a NURBS curve.
+    #[test]
+    fn transform_uniform_scale() {
+        let mut nurbs = TC::new(
+            1,
+            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
+            vec![1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        nurbs
+            .transform(&Similarity2::from_scaling(2.0).to_homogeneous())
+            .unwrap();
+
+        let expected = TC::new(
+            1,
+            vec![Vector2::new(0.0, 0.0), Vector2::new(2.0 * 42.0, 2.0 * 56.0)],
+            vec![1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(nurbs, expected);
+    }
+
+    /// A projective transformation of a circle is exactly the projection of
+    /// every point on the circle.
+    #[test]
+    fn transform_projective() {
+        let matrix = Matrix3::new(2.0, 0.5, 1.0, -0.3, 1.5, 0.2, 0.1, 0.2, 3.0);
+        let project = |p: Vector2<f64>| {
+            Point::from_homogeneous(matrix * Point::from(p).to_homogeneous())
+                .unwrap()
+                .coords
+        };
+
+        let circle = unit_circle();
+        let mut transformed = circle.clone();
+        transformed.transform(&matrix).unwrap();
+        assert!(transformed.weights().iter().all(|&w| w > 0.0));
+        for i in 0..101 {
+            let u = (i as f64) / 100.0;
+            assert_relative_eq!(
+                transformed.de_boor(u),
+                project(circle.de_boor(u)),
+                epsilon = 1e-9
+            );
+        }
+
+        // negating the whole matrix does not change the transformation
+        let mut negated = circle.clone();
+        negated.transform(&-matrix).unwrap();
+        assert_same_geometry(&negated, &transformed);
+    }
+
+    /// A perspective projection which places some control points behind the
+    /// viewer cannot be applied.
+    #[test]
+    fn transform_behind_viewer() {
+        let mut circle = unit_circle();
+        let matrix = Matrix3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.5);
+        assert_eq!(
+            circle.transform(&matrix),
+            Err(CurveError::TransformedWeightNotPositive)
+        );
+        assert_eq!(circle, unit_circle());
+    }
+
+    /// Test de Boor evalutaion on a non-rational, uniform Bezier.
+    #[test]
+    fn de_boor_non_rational_uniform_bezier() {
+        let test_curve = TC::new(
+            3,
+            vec![
+                Vector2::new(-10.0, 10.0),
+                Vector2::new(10.0, 10.0),
+                Vector2::new(-10.0, -10.0),
+                Vector2::new(10.0, -10.0),
+            ],
+            vec![1.0, 1.0, 1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+
+        // tests for in-range parameter
+        assert_relative_eq!(Vector2::new(-10.0, 10.0), test_curve.de_boor(0.0));
+        assert_relative_eq!(Vector2::new(-2.16, 7.92), test_curve.de_boor(0.2));
+        assert_relative_eq!(Vector2::new(0.0, 0.0), test_curve.de_boor(0.5));
+        assert_relative_eq!(Vector2::new(10.0, -10.0), test_curve.de_boor(1.0));
+
+        // tests with parameter out-of-range (clipped to parameter range)
+        assert_relative_eq!(Vector2::new(-10.0, 10.0), test_curve.de_boor(-1.0));
+        assert_relative_eq!(Vector2::new(10.0, -10.0), test_curve.de_boor(2.0));
+    }
+
+    /// Test Curve with double-precision values, for checking numerical
+    /// results more tightly.
+    pub(super) type TC64 = Curve<f64, Vector2<f64>>;
+
+    /// Unit circle, from the examples.
+    pub(super) fn unit_circle() -> TC64 {
+        let r = f64::sqrt(2.0) / 2.0;
+        TC64::new(
+            2,
+            vec![
+                Vector2::new(1.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(0.0, 1.0),
+                Vector2::new(-1.0, 1.0),
+                Vector2::new(-1.0, 0.0),
+                Vector2::new(-1.0, -1.0),
+                Vector2::new(0.0, -1.0),
+                Vector2::new(1.0, -1.0),
+                Vector2::new(1.0, 0.0),
+            ],
+            vec![1.0, r, 1.0, r, 1.0, r, 1.0, r, 1.0],
+            KnotVec::new(vec![
+                0.0, 0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1.0, 1.0, 1.0,
+            ])
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Reed leaf hieroglyph, from the examples.
+    pub(super) fn reed_leaf() -> TC64 {
+        TC64::new(
+            3,
+            vec![
+                Vector2::new(152.0, 18.0),
+                Vector2::new(140.0, 24.0),
+                Vector2::new(130.0, 29.0),
+                Vector2::new(121.0, 41.0),
+                Vector2::new(105.0, 65.0),
+                Vector2::new(105.0, 96.0),
+                Vector2::new(107.0, 282.0),
+                Vector2::new(107.0, 282.0),
+                Vector2::new(125.0, 277.0),
+                Vector2::new(125.0, 277.0),
+                Vector2::new(125.0, 267.0),
+                Vector2::new(123.0, 235.0),
+                Vector2::new(129.0, 230.0),
+                Vector2::new(140.0, 221.0),
+                Vector2::new(158.0, 209.0),
+                Vector2::new(173.0, 201.0),
+                Vector2::new(173.0, 201.0),
+                Vector2::new(152.0, 18.0),
+                Vector2::new(152.0, 18.0),
+            ],
+            vec![1.0; 19],
+            KnotVec::new(vec![
+                0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 4.0,
+                5.0, 5.0, 5.0, 6.0, 6.0, 6.0, 6.0,
+            ])
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Checks analytic derivatives against central finite differences of the
+    /// next-lowest derivative, at parameter values away from the knots.
+    fn check_derivatives_against_finite_differences(curve: &TC64, n: usize, us: &[f64]) {
+        let h = 1e-6;
+        for &u in us {
+            let ders = curve.derivatives(u, n);
+            assert_eq!(ders.len(), n + 1);
+            assert_relative_eq!(ders[0], curve.de_boor(u), epsilon = 1e-12);
+
+            let ders_lo = curve.derivatives(u - h, n);
+            let ders_hi = curve.derivatives(u + h, n);
+            for k in 1..n + 1 {
+                let fd = (ders_hi[k - 1] - ders_lo[k - 1]) / (2.0 * h);
+                let scale = 1.0 + ders[k].norm();
+                assert_relative_eq!(ders[k] / scale, fd / scale, epsilon = 1e-5);
+            }
+        }
+    }
+
+    /// Derivatives of the unit circle, including derivatives of higher order
+    /// than the degree of the curve.
+    #[test]
+    fn derivatives_circle() {
+        let circle = unit_circle();
+        check_derivatives_against_finite_differences(
+            &circle,
+            4,
+            &[0.05, 0.1, 0.3, 0.45, 0.6, 0.7, 0.8, 0.99],
+        );
+
+        // the tangent of a circle is perpendicular to its radius
+        for &u in &[0.0, 0.1, 0.25, 0.4, 0.5, 0.9, 1.0] {
+            let ders = circle.derivatives(u, 1);
+            assert_relative_eq!(ders[0].dot(&ders[1]), 0.0, epsilon = 1e-12);
+        }
+    }
+
+    /// Derivatives of the reed leaf, which has several polynomial segments.
+    #[test]
+    fn derivatives_reed_leaf() {
+        check_derivatives_against_finite_differences(
+            &reed_leaf(),
+            3,
+            &[0.1, 0.5, 1.3, 1.9, 2.2, 3.5, 4.01, 4.7, 5.5, 5.99],
+        );
+    }
+
+    /// Derivatives of order higher than the degree of a polynomial curve are
+    /// zero.
+    #[test]
+    fn derivatives_polynomial_high_order() {
+        let ders = reed_leaf().derivatives(0.5, 5);
+        assert_eq!(ders[4], Vector2::new(0.0, 0.0));
+        assert_eq!(ders[5], Vector2::new(0.0, 0.0));
+    }
+
+    /// Checks that two curves are geometrically identical by evaluating them
+    /// at evenly-spaced parameter values.
+    fn assert_same_geometry(a: &TC64, b: &TC64) {
+        assert_eq!(a.knots().min_u(), b.knots().min_u());
+        assert_eq!(a.knots().max_u(), b.knots().max_u());
+        let n = 200;
+        for i in 0..n + 1 {
+            let u = a.knots().min_u()
+                + (a.knots().max_u() - a.knots().min_u()) * (i as f64) / (n as f64);
+            assert_relative_eq!(a.de_boor(u), b.de_boor(u), epsilon = 1e-9);
+        }
+    }
+
+    /// Inserting knots into a rational curve.
+    #[test]
+    fn insert_knot_circle() {
+        let circle = unit_circle();
+
+        let once = circle.insert_knot(0.1, 1).unwrap();
+        assert_eq!(once.control_points().len(), 10);
+        assert_eq!(once.weights().len(), 10);
+        assert_eq!(once.knots().multiplicity(0.1), 1);
+        assert_same_geometry(&circle, &once);
+
+        let twice = circle.insert_knot(0.6, 2).unwrap();
+        assert_eq!(twice.control_points().len(), 11);
+        assert_eq!(twice.knots().multiplicity(0.6), 2);
+        assert_same_geometry(&circle, &twice);
+    }
+
+    /// Inserting knots into a polynomial curve, including at existing knots.
+    #[test]
+    fn insert_knot_reed_leaf() {
+        let leaf = reed_leaf();
+        let inserted = leaf
+            .insert_knot(2.0, 0)
+            .unwrap()
+            .insert_knot(0.5, 3)
+            .unwrap()
+            .insert_knot(4.25, 1)
+            .unwrap()
+            .insert_knot(4.25, 2)
+            .unwrap();
+        assert_eq!(
+            inserted.control_points().len(),
+            leaf.control_points().len() + 6
+        );
+        assert_same_geometry(&leaf, &inserted);
+
+        // inserting an existing knot, up to the degree of the curve
+        let circle = unit_circle();
+        let inserted = circle.insert_knot(0.5, 0).unwrap();
+        assert_eq!(inserted, circle);
+    }
+
+    /// Knot multiplicity cannot exceed the degree.
+    #[test]
+    fn insert_knot_exceeding_degree() {
+        assert_eq!(
+            unit_circle().insert_knot(0.25, 1),
+            Err(CurveError::KnotMultiplicityExceedsDegree {
+                degree: 2,
+                multiplicity: 3
+            })
+        );
+        assert_eq!(
+            reed_leaf().insert_knot(0.5, 4),
+            Err(CurveError::KnotMultiplicityExceedsDegree {
+                degree: 3,
+                multiplicity: 4
+            })
+        );
+        assert_eq!(
+            unit_circle().insert_knot(0.0, 1),
+            Err(CurveError::KnotMultiplicityExceedsDegree {
+                degree: 2,
+                multiplicity: 4
+            })
+        );
+    }
+
+    /// Knots cannot be inserted outside the parameter range.
+    #[test]
+    fn insert_knot_out_of_range() {
+        assert_eq!(
+            unit_circle().insert_knot(1.5, 1),
+            Err(CurveError::ParameterOutOfRange)
+        );
+    }
+
+    /// Refining knots is equivalent to inserting them one at a time.
+    #[test]
+    fn refine_knots_matches_insertion() {
+        let leaf = reed_leaf();
+        let refined = leaf.refine_knots(&[0.5, 0.5, 2.5, 3.25, 5.5]).unwrap();
+        let inserted = leaf
+            .insert_knot(0.5, 2)
+            .unwrap()
+            .insert_knot(2.5, 1)
+            .unwrap()
+            .insert_knot(3.25, 1)
+            .unwrap()
+            .insert_knot(5.5, 1)
+            .unwrap();
+        assert_eq!(refined.knots(), inserted.knots());
+        for (a, b) in refined
+            .control_points()
+            .iter()
+            .zip(inserted.control_points().iter())
+        {
+            assert_relative_eq!(a, b, epsilon = 1e-9);
+        }
+
+        // refining with no knots leaves the curve unchanged
+        assert_eq!(leaf.refine_knots(&[]).unwrap(), leaf);
+    }
+
+    /// Refining knots of a curve with simple knots, including at an existing
+    /// knot.
+    #[test]
+    fn refine_knots_simple_knots() {
+        let curve = TC64::new(
+            3,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 2.0),
+                Vector2::new(3.0, 1.0),
+                Vector2::new(4.0, 4.0),
+                Vector2::new(6.0, 0.0),
+                Vector2::new(7.0, 3.0),
             ],
-            vec![1.0, 1.0, 1.0, 1.0],
-            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+            vec![1.0, 0.5, 2.0, 1.0, 1.5, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 0.4, 0.7, 1.0, 1.0, 1.0, 1.0]).unwrap(),
         )
         .unwrap();
+        let refined = curve.refine_knots(&[0.2, 0.4, 0.4, 0.5, 0.9]).unwrap();
+        assert_eq!(refined.knots().multiplicity(0.4), 3);
+        assert_same_geometry(&curve, &refined);
+    }
 
-        // tests for in-range parameter
-        assert_relative_eq!(Vector2::new(-10.0, 10.0), test_curve.de_boor(0.0));
-        assert_relative_eq!(Vector2::new(-2.16, 7.92), test_curve.de_boor(0.2));
-        assert_relative_eq!(Vector2::new(0.0, 0.0), test_curve.de_boor(0.5));
-        assert_relative_eq!(Vector2::new(10.0, -10.0), test_curve.de_boor(1.0));
+    /// Invalid knot refinements.
+    #[test]
+    fn refine_knots_invalid() {
+        let circle = unit_circle();
+        assert_eq!(
+            circle.refine_knots(&[0.5, 0.1]),
+            Err(CurveError::UnsortedKnots)
+        );
+        assert_eq!(
+            circle.refine_knots(&[0.1, 1.1]),
+            Err(CurveError::ParameterOutOfRange)
+        );
+        assert_eq!(
+            circle.refine_knots(&[0.1, 0.1, 0.1]),
+            Err(CurveError::KnotMultiplicityExceedsDegree {
+                degree: 2,
+                multiplicity: 3
+            })
+        );
+        assert_eq!(
+            circle.refine_knots(&[0.1, 0.75]),
+            Err(CurveError::KnotMultiplicityExceedsDegree {
+                degree: 2,
+                multiplicity: 3
+            })
+        );
+    }
 
-        // tests with parameter out-of-range (clipped to parameter range)
-        assert_relative_eq!(Vector2::new(-10.0, 10.0), test_curve.de_boor(-1.0));
-        assert_relative_eq!(Vector2::new(10.0, -10.0), test_curve.de_boor(2.0));
+    prop_compose! {
+        fn arb_refinement(min_u: f64, max_u: f64)
+                         (mut ks in proptest::collection::vec(min_u..max_u, 0..32)) -> Vec<f64>
+        {
+            ks.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            ks.dedup();
+            ks
+        }
+    }
+
+    proptest! {
+        /// Refining the knots of the circle does not change its geometry.
+        #[test]
+        fn refine_knots_circle(new_knots in arb_refinement(0.0, 1.0)) {
+            let circle = unit_circle();
+            prop_assume!(new_knots.iter().all(|&u| circle.knots().multiplicity(u) == 0));
+            let refined = circle.refine_knots(&new_knots).unwrap();
+            assert_eq!(refined.knots().len(), circle.knots().len() + new_knots.len());
+            assert_same_geometry(&circle, &refined);
+        }
+
+        /// Refining the knots of the reed leaf does not change its geometry.
+        #[test]
+        fn refine_knots_reed_leaf(new_knots in arb_refinement(0.0, 6.0)) {
+            let leaf = reed_leaf();
+            prop_assume!(new_knots.iter().all(|&u| leaf.knots().multiplicity(u) == 0));
+            let refined = leaf.refine_knots(&new_knots).unwrap();
+            assert_same_geometry(&leaf, &refined);
+        }
+    }
+
+    /// Returns the maximum distance between two curves, measured at the same
+    /// parameter values.
+    fn max_parametric_distance(a: &TC64, b: &TC64) -> f64 {
+        let n = 1000;
+        (0..n + 1)
+            .map(|i| {
+                let u = a.knots().min_u()
+                    + (a.knots().max_u() - a.knots().min_u()) * (i as f64) / (n as f64);
+                (a.de_boor(u) - b.de_boor(u)).norm()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Removing knots which were previously inserted recovers the original
+    /// curve.
+    #[test]
+    fn remove_knot_inserted() {
+        let circle = unit_circle();
+        let inserted = circle.refine_knots(&[0.1, 0.1, 0.6]).unwrap();
+
+        let (removed, n) = inserted.remove_knot(0.1, 2, 1e-9).unwrap();
+        assert_eq!(n, 2);
+        let (removed, n) = removed.remove_knot(0.6, 5, 1e-9).unwrap();
+        assert_eq!(n, 1);
+
+        assert_eq!(removed.knots(), circle.knots());
+        for (a, b) in removed.control_points().iter().zip(circle.control_points()) {
+            assert_relative_eq!(a, b, epsilon = 1e-9);
+        }
+        for (a, b) in removed.weights().iter().zip(circle.weights()) {
+            assert_relative_eq!(a, b, epsilon = 1e-9);
+        }
+    }
+
+    /// Knots which are required to represent the curve are not removed unless
+    /// the tolerance allows it.
+    #[test]
+    fn remove_knot_tolerance() {
+        let circle = unit_circle();
+        let (removed, n) = circle.remove_knot(0.5, 2, 1e-6).unwrap();
+        assert_eq!(n, 0);
+        assert_eq!(removed, circle);
+
+        let tolerance = 2.0;
+        let (removed, n) = circle.remove_knot(0.5, 1, tolerance).unwrap();
+        assert_eq!(n, 1);
+        let distance = max_parametric_distance(&circle, &removed);
+        assert!(distance > 1e-3);
+        assert!(distance <= tolerance);
+    }
+
+    /// Only interior knots can be removed.
+    #[test]
+    fn remove_knot_invalid() {
+        let circle = unit_circle();
+        assert_eq!(
+            circle.remove_knot(0.0, 1, 1.0),
+            Err(CurveError::ParameterOutOfRange)
+        );
+        assert_eq!(
+            circle.remove_knot(0.3, 1, 1.0),
+            Err(CurveError::KnotNotFound)
+        );
+    }
+
+    /// Simplifying the knots of a curve removes redundant knots.
+    #[test]
+    fn simplify_knots() {
+        let leaf = reed_leaf();
+        let refined = leaf.refine_knots(&[0.3, 0.6, 2.5, 2.5, 5.1]).unwrap();
+        let (simplified, n) = refined.simplify_knots(1e-9);
+        assert!(n >= 5);
+        assert!(simplified.knots().len() <= leaf.knots().len());
+        assert_same_geometry(&leaf, &simplified);
+
+        // simplifying with a large tolerance stays within the tolerance
+        let tolerance = 2.0;
+        let (simplified, n) = unit_circle().simplify_knots(tolerance);
+        assert!(n > 0);
+        assert!(max_parametric_distance(&unit_circle(), &simplified) <= tolerance);
+    }
+
+    /// Elevating the degree of the circle.
+    #[test]
+    fn elevate_degree_circle() {
+        let circle = unit_circle();
+        let elevated = circle.elevate_degree(1);
+        assert_eq!(elevated.degree(), 3);
+        assert_eq!(
+            elevated.knots(),
+            &KnotVec::new(vec![
+                0.0, 0.0, 0.0, 0.0, 0.25, 0.25, 0.25, 0.5, 0.5, 0.5, 0.75, 0.75, 0.75, 1.0, 1.0,
+                1.0, 1.0
+            ])
+            .unwrap()
+        );
+        assert_same_geometry(&circle, &elevated);
+
+        assert_eq!(circle.elevate_degree(0), circle);
+    }
+
+    /// Elevating the degree of a curve with knots of different multiplicities.
+    #[test]
+    fn elevate_degree_multiplicities() {
+        let curve = reed_leaf().refine_knots(&[0.5, 2.25, 2.25]).unwrap();
+        let elevated = curve.elevate_degree(2);
+        assert_eq!(elevated.degree(), 5);
+        assert_eq!(elevated.knots().multiplicity(0.0), 6);
+        assert_eq!(elevated.knots().multiplicity(0.5), 3);
+        assert_eq!(elevated.knots().multiplicity(1.0), 5);
+        assert_eq!(elevated.knots().multiplicity(2.25), 4);
+        assert_eq!(elevated.knots().multiplicity(6.0), 6);
+        assert_same_geometry(&curve, &elevated);
+    }
+
+    /// Two straight lines, which form a single curve that is broken where
+    /// the interior knot has a multiplicity of `degree + 1`.
+    fn broken_lines() -> TC64 {
+        TC64::new(
+            1,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(2.0, 0.0),
+                Vector2::new(3.0, 1.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0]).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Degree elevation does not change the geometry or parameterization
+    /// of a curve.
+    #[test]
+    fn elevate_degree_equivalent() {
+        let params = proptest::collection::vec(0.0..6.0, 16);
+        proptest!(|(t in 1usize..4, us in params)| {
+            let leaf = reed_leaf();
+            let elevated = leaf.elevate_degree(t);
+            assert_eq!(elevated.degree(), leaf.degree() + t);
+            let broken = broken_lines();
+            let broken_elevated = broken.elevate_degree(t);
+            for u in us {
+                assert_relative_eq!(leaf.de_boor(u), elevated.de_boor(u), epsilon = 1e-9);
+                assert_relative_eq!(
+                    unit_circle().de_boor(u / 6.0),
+                    unit_circle().elevate_degree(t).de_boor(u / 6.0),
+                    epsilon = 1e-12
+                );
+                assert_relative_eq!(
+                    broken.de_boor(u / 3.0),
+                    broken_elevated.de_boor(u / 3.0),
+                    epsilon = 1e-12
+                );
+            }
+        });
+    }
+
+    /// Elevating the degree of a curve which is broken at a knot keeps it
+    /// broken there.
+    #[test]
+    fn elevate_degree_broken() {
+        let curve = broken_lines();
+        let elevated = curve.elevate_degree(2);
+        assert_eq!(elevated.degree(), 3);
+        assert_eq!(elevated.knots().multiplicity(1.0), 4);
+        assert_eq!(elevated.control_points().len(), 8);
+        assert_same_geometry(&curve, &elevated);
+    }
+
+    /// Reducing the degree of curves which were elevated recovers the
+    /// original curves, in one step or several.
+    #[test]
+    fn reduce_degree_elevated() {
+        for curve in &[unit_circle(), reed_leaf()] {
+            for t in 1..5 {
+                let mut reduced = curve.elevate_degree(t);
+                let mut deviation = 0.0;
+                for _ in 0..t {
+                    let (r, e) = reduced.reduce_degree(1e-9 - deviation).unwrap();
+                    reduced = r;
+                    deviation += e;
+                }
+                assert!(deviation <= 1e-9);
+                assert_eq!(reduced.degree(), curve.degree());
+                assert_eq!(reduced.knots(), curve.knots());
+                assert_same_geometry(curve, &reduced);
+
+                // the original curve cannot be reduced within the tolerance
+                assert_eq!(
+                    reduced.reduce_degree(1e-9),
+                    Err(CurveError::DegreeReductionToleranceExceeded)
+                );
+            }
+        }
+    }
+
+    /// Reducing the degree of a curve approximately.
+    #[test]
+    fn reduce_degree_approximate() {
+        let curve = TC64::new(
+            4,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 2.0),
+                Vector2::new(3.0, 1.0),
+                Vector2::new(4.0, 4.0),
+                Vector2::new(6.0, 0.0),
+                Vector2::new(7.0, 3.0),
+                Vector2::new(8.0, 1.0),
+            ],
+            vec![1.0, 1.2, 0.8, 1.0, 1.5, 1.0, 1.0],
+            KnotVec::new(vec![
+                0.0, 0.0, 0.0, 0.0, 0.0, 0.4, 0.7, 1.0, 1.0, 1.0, 1.0, 1.0,
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+
+        let tolerance = 2.0;
+        let (reduced, deviation) = curve.reduce_degree(tolerance).unwrap();
+        assert_eq!(reduced.degree(), 3);
+        assert!(deviation <= tolerance);
+        let distance = max_parametric_distance(&curve, &reduced);
+        assert!(distance > 1e-6);
+        assert!(distance <= deviation);
+
+        assert_eq!(
+            curve.reduce_degree(1e-3),
+            Err(CurveError::DegreeReductionToleranceExceeded)
+        );
+
+        // reducing again accumulates the deviation of each step
+        let (quadratic, step) = reduced.reduce_degree(100.0).unwrap();
+        assert_eq!(quadratic.degree(), 2);
+        assert!(max_parametric_distance(&curve, &quadratic) <= deviation + step);
+    }
+
+    /// The degree of a linear curve cannot be reduced.
+    #[test]
+    fn reduce_degree_linear() {
+        let line = TC64::new(
+            1,
+            vec![Vector2::new(0.0, 0.0), Vector2::new(42.0, 56.0)],
+            vec![1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(line.reduce_degree(1.0), Err(CurveError::InvalidDegree));
+    }
+
+    /// Decomposing a curve which is broken at a knot gives segments which do
+    /// not meet there.
+    #[test]
+    fn to_bezier_segments_broken() {
+        let segments = broken_lines().to_bezier_segments();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(
+            segments[0].control_points(),
+            &[Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)]
+        );
+        assert_eq!(
+            segments[1].control_points(),
+            &[Vector2::new(2.0, 0.0), Vector2::new(3.0, 1.0)]
+        );
+        assert_eq!(segments[1].knots().as_slice(), &[1.0, 1.0, 2.0, 2.0]);
+    }
+
+    /// Decomposing the reed leaf gives 2 line segments and 4 cubic Bézier
+    /// curves.
+    #[test]
+    fn to_bezier_segments_reed_leaf() {
+        let leaf = reed_leaf();
+        let segments = leaf.to_bezier_segments();
+        assert_eq!(segments.len(), 6);
+        for (i, segment) in segments.iter().enumerate() {
+            assert_eq!(segment.degree(), 3);
+            assert_eq!(segment.control_points().len(), 4);
+            assert_eq!(segment.knots().min_u(), i as f64);
+            assert_eq!(segment.knots().max_u(), (i + 1) as f64);
+            for j in 0..11 {
+                let u = i as f64 + (j as f64) / 10.0;
+                assert_relative_eq!(segment.de_boor(u), leaf.de_boor(u), epsilon = 1e-9);
+            }
+        }
+
+        // the control points of the line segments lie at their ends
+        assert_eq!(
+            segments[2].control_points(),
+            &vec![
+                Vector2::new(107.0, 282.0),
+                Vector2::new(107.0, 282.0),
+                Vector2::new(125.0, 277.0),
+                Vector2::new(125.0, 277.0),
+            ]
+        );
+    }
+
+    /// Decomposing the circle gives four rational quarter-circles.
+    #[test]
+    fn to_bezier_segments_circle() {
+        let segments = unit_circle().to_bezier_segments();
+        assert_eq!(segments.len(), 4);
+        let r = f64::sqrt(2.0) / 2.0;
+        for segment in &segments {
+            assert_eq!(segment.weights(), &vec![1.0, r, 1.0]);
+        }
+        assert_eq!(
+            segments[1].control_points(),
+            &vec![
+                Vector2::new(0.0, 1.0),
+                Vector2::new(-1.0, 1.0),
+                Vector2::new(-1.0, 0.0),
+            ]
+        );
+    }
+
+    /// A curve with simple knots is decomposed by inserting knots.
+    #[test]
+    fn to_bezier_segments_simple_knots() {
+        let curve = unit_circle().elevate_degree(1).simplify_knots(1e-9).0;
+        let segments = curve.to_bezier_segments();
+        assert_eq!(segments.len(), 4);
+        for segment in &segments {
+            for j in 0..11 {
+                let u = segment.knots().min_u()
+                    + (segment.knots().max_u() - segment.knots().min_u()) * (j as f64) / 10.0;
+                assert_relative_eq!(segment.de_boor(u), curve.de_boor(u), epsilon = 1e-9);
+            }
+        }
+    }
+
+    /// Checks that a curve matches a reference curve over its own parameter
+    /// range.
+    fn assert_matches_over_range(curve: &TC64, reference: &TC64) {
+        let (u0, u1) = (curve.knots().min_u(), curve.knots().max_u());
+        assert!(curve.knots().is_clamped(curve.degree()));
+        for i in 0..101 {
+            let u = u0 + (u1 - u0) * (i as f64) / 100.0;
+            assert_relative_eq!(curve.de_boor(u), reference.de_boor(u), epsilon = 1e-9);
+        }
+    }
+
+    /// Splitting curves at parameter values.
+    #[test]
+    fn split_at() {
+        let circle = unit_circle();
+        for &u in &[0.1, 0.25, 0.3, 0.9] {
+            let (left, right) = circle.split_at(u).unwrap();
+            assert_eq!(left.knots().min_u(), 0.0);
+            assert_eq!(left.knots().max_u(), u);
+            assert_eq!(right.knots().min_u(), u);
+            assert_eq!(right.knots().max_u(), 1.0);
+            assert_matches_over_range(&left, &circle);
+            assert_matches_over_range(&right, &circle);
+        }
+
+        let leaf = reed_leaf();
+        for &u in &[0.5, 2.0, 5.9] {
+            let (left, right) = leaf.split_at(u).unwrap();
+            assert_matches_over_range(&left, &leaf);
+            assert_matches_over_range(&right, &leaf);
+        }
+
+        // splitting at an existing knot with full multiplicity does not add
+        // any control points
+        let (left, right) = leaf.split_at(2.0).unwrap();
+        assert_eq!(
+            left.control_points().len() + right.control_points().len(),
+            leaf.control_points().len() + 1
+        );
+
+        // a curve which is broken at u splits into its two pieces
+        let (left, right) = broken_lines().split_at(1.0).unwrap();
+        assert_eq!(
+            left.control_points(),
+            &[Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)]
+        );
+        assert_eq!(left.knots().as_slice(), &[0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(
+            right.control_points(),
+            &[Vector2::new(2.0, 0.0), Vector2::new(3.0, 1.0)]
+        );
+        assert_eq!(right.knots().as_slice(), &[1.0, 1.0, 2.0, 2.0]);
+    }
+
+    /// Curves cannot be split at or beyond their ends.
+    #[test]
+    fn split_at_out_of_range() {
+        let circle = unit_circle();
+        assert_eq!(circle.split_at(0.0), Err(CurveError::ParameterOutOfRange));
+        assert_eq!(circle.split_at(1.0), Err(CurveError::ParameterOutOfRange));
+        assert_eq!(circle.split_at(-0.5), Err(CurveError::ParameterOutOfRange));
+    }
+
+    /// Extracting sub-curves.
+    #[test]
+    fn subcurve() {
+        let leaf = reed_leaf();
+        let sub = leaf.subcurve(0.5, 4.5).unwrap();
+        assert_eq!(sub.knots().min_u(), 0.5);
+        assert_eq!(sub.knots().max_u(), 4.5);
+        assert_matches_over_range(&sub, &leaf);
+
+        assert_matches_over_range(&leaf.subcurve(0.0, 3.5).unwrap(), &leaf);
+        assert_matches_over_range(&leaf.subcurve(3.5, 6.0).unwrap(), &leaf);
+        assert_eq!(leaf.subcurve(0.0, 6.0).unwrap(), leaf);
+
+        // sub-curves of periodic curves are clamped, even over the whole range
+        let periodic = periodic_cubic();
+        for &(u0, u1) in &[(0.0, 5.0), (0.0, 2.0), (2.0, 5.0)] {
+            let sub = periodic.subcurve(u0, u1).unwrap();
+            assert_eq!(sub.kind(), CurveKind::Clamped);
+            assert_matches_over_range(&sub, &periodic);
+        }
+
+        assert_eq!(
+            leaf.subcurve(2.0, 2.0),
+            Err(CurveError::DegenerateParameterRange)
+        );
+        assert_eq!(
+            leaf.subcurve(3.0, 1.0),
+            Err(CurveError::DegenerateParameterRange)
+        );
+        assert_eq!(
+            leaf.subcurve(-1.0, 1.0),
+            Err(CurveError::ParameterOutOfRange)
+        );
+        assert_eq!(
+            leaf.subcurve(1.0, 7.0),
+            Err(CurveError::ParameterOutOfRange)
+        );
+    }
+
+    /// Reversing the direction of curves.
+    #[test]
+    fn reversed() {
+        for curve in &[unit_circle(), reed_leaf()] {
+            let reversed = curve.reversed();
+            let (u0, u1) = (curve.knots().min_u(), curve.knots().max_u());
+            assert_eq!(reversed.knots().min_u(), u0);
+            assert_eq!(reversed.knots().max_u(), u1);
+            for i in 0..101 {
+                let u = u0 + (u1 - u0) * (i as f64) / 100.0;
+                assert_relative_eq!(
+                    reversed.de_boor(u0 + u1 - u),
+                    curve.de_boor(u),
+                    epsilon = 1e-9
+                );
+            }
+            assert_eq!(&reversed.reversed(), curve);
+        }
+    }
+
+    /// Changing the parameter range of a curve.
+    #[test]
+    fn reparameterize() {
+        let leaf = reed_leaf();
+        let reparameterized = leaf.reparameterize(-1.0, 2.0).unwrap();
+        assert_eq!(reparameterized.knots().min_u(), -1.0);
+        assert_eq!(reparameterized.knots().max_u(), 2.0);
+        assert_eq!(reparameterized.knots().multiplicity(0.5), 3);
+        for i in 0..101 {
+            let s = (i as f64) / 100.0;
+            assert_relative_eq!(
+                reparameterized.de_boor(-1.0 + 3.0 * s),
+                leaf.de_boor(6.0 * s),
+                epsilon = 1e-9
+            );
+        }
+
+        assert_eq!(
+            leaf.reparameterize(1.0, 1.0),
+            Err(CurveError::DegenerateParameterRange)
+        );
+    }
+
+    /// Möbius reparameterization changes the parameterization but not the
+    /// shape of a curve.
+    #[test]
+    fn mobius_reparameterize() {
+        for curve in &[unit_circle(), reed_leaf(), unit_circle().elevate_degree(2)] {
+            let (u0, u1) = (curve.knots().min_u(), curve.knots().max_u());
+            for &c in &[0.25, 1.0, 3.0] {
+                let reparameterized = curve.mobius_reparameterize(c).unwrap();
+                assert_eq!(reparameterized.knots().min_u(), u0);
+                assert_eq!(reparameterized.knots().max_u(), u1);
+                for i in 0..101 {
+                    let s = (i as f64) / 100.0;
+                    let t = s / (s + c * (1.0 - s));
+                    assert_relative_eq!(
+                        reparameterized.de_boor(u0 + (u1 - u0) * s),
+                        curve.de_boor(u0 + (u1 - u0) * t),
+                        epsilon = 1e-9
+                    );
+                }
+            }
+        }
+
+        assert_eq!(
+            unit_circle().mobius_reparameterize(0.0),
+            Err(CurveError::InvalidMobiusFactor)
+        );
+    }
+
+    /// Points sampled from a helix, with uneven spacing.
+    pub(super) fn helix_points() -> Vec<Vector3<f64>> {
+        [0.0, 0.3, 0.5, 1.2, 2.0, 2.1, 2.9, 4.0, 5.5]
+            .iter()
+            .map(|&t: &f64| Vector3::new(t.cos(), t.sin(), 0.2 * t))
+            .collect()
+    }
+
+    /// Smooth closed cubic curve, stored in periodic form.
+    pub(super) fn periodic_cubic() -> TC64 {
+        let points = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, -1.0),
+            Vector2::new(4.0, 1.0),
+            Vector2::new(3.0, 4.0),
+            Vector2::new(0.0, 3.0),
+        ];
+        let mut control_points = points.clone();
+        control_points.extend_from_slice(&points[..3]);
+        let knots = (0..12).map(|i| (i as f64) - 3.0).collect();
+        TC64::new(
+            3,
+            control_points,
+            vec![1.0; 8],
+            KnotVec::new(knots).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Periodic curves are evaluated over their parameter range, and join
+    /// smoothly where their ends meet.
+    #[test]
+    fn periodic_curve() {
+        let curve = periodic_cubic();
+        assert_eq!(curve.kind(), CurveKind::Periodic);
+        assert_eq!(curve.min_u(), 0.0);
+        assert_eq!(curve.max_u(), 5.0);
+
+        let start = curve.derivatives(0.0, 2);
+        let end = curve.derivatives(5.0, 2);
+        for k in 0..3 {
+            assert_relative_eq!(start[k], end[k], epsilon = 1e-12);
+        }
+        assert_eq!(curve.de_boor(-1.0), curve.de_boor(0.0));
+        assert_eq!(curve.de_boor(6.0), curve.de_boor(5.0));
+
+        assert_eq!(unit_circle().kind(), CurveKind::Clamped);
+        let mut control_points = curve.control_points().clone();
+        control_points[6] = Vector2::new(2.0, -1.5);
+        let unclamped = TC64::new(3, control_points, vec![1.0; 8], curve.knots().clone()).unwrap();
+        assert_eq!(unclamped.kind(), CurveKind::Unclamped);
+    }
+
+    /// Converting between clamped, unclamped and periodic curves.
+    #[test]
+    fn clamp_and_unclamp() {
+        let curve = periodic_cubic();
+        let clamped = curve.clamp();
+        assert_eq!(clamped.kind(), CurveKind::Clamped);
+        assert_eq!(clamped.min_u(), 0.0);
+        assert_eq!(clamped.max_u(), 5.0);
+        assert_eq!(clamped.control_points().len(), 8);
+        assert_relative_eq!(
+            clamped.control_points()[0],
+            curve.de_boor(0.0),
+            epsilon = 1e-12
+        );
+
+        let unclamped = clamped.unclamp().unwrap();
+        assert_eq!(unclamped.kind(), CurveKind::Periodic);
+        for (a, b) in unclamped
+            .control_points()
+            .iter()
+            .zip(curve.control_points())
+        {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+        for (a, b) in unclamped
+            .knots()
+            .as_slice()
+            .iter()
+            .zip(curve.knots().as_slice())
+        {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+
+        // curves which are not smooth where their ends meet become unclamped
+        for curve in &[unit_circle(), reed_leaf()] {
+            let unclamped = curve.unclamp().unwrap();
+            assert_eq!(unclamped.kind(), CurveKind::Unclamped);
+            let (u0, u1) = (curve.min_u(), curve.max_u());
+            assert_eq!(unclamped.min_u(), u0);
+            assert_eq!(unclamped.max_u(), u1);
+            for i in 0..101 {
+                let u = u0 + (u1 - u0) * (i as f64) / 100.0;
+                assert_relative_eq!(unclamped.de_boor(u), curve.de_boor(u), epsilon = 1e-9);
+            }
+
+            let clamped = unclamped.clamp();
+            assert_eq!(clamped.knots(), curve.knots());
+            for (a, b) in clamped.control_points().iter().zip(curve.control_points()) {
+                assert_relative_eq!(a, b, epsilon = 1e-9);
+            }
+        }
+    }
+
+    /// Operations on unclamped curves match the equivalent clamped curves.
+    #[test]
+    fn unclamped_operations() {
+        let curve = periodic_cubic();
+        let clamped = curve.clamp();
+        let check = |a: &TC64, b: &TC64| {
+            let (u0, u1) = (a.min_u(), a.max_u());
+            assert_eq!(b.min_u(), u0);
+            assert_eq!(b.max_u(), u1);
+            for i in 0..51 {
+                let u = u0 + (u1 - u0) * (i as f64) / 50.0;
+                assert_relative_eq!(a.de_boor(u), b.de_boor(u), epsilon = 1e-9);
+            }
+        };
+
+        check(&curve.insert_knot(2.5, 2).unwrap(), &clamped);
+        check(&curve.elevate_degree(1), &clamped);
+        check(&curve.reversed().reversed(), &clamped);
+        check(&curve.reparameterize(0.0, 5.0).unwrap(), &clamped);
+        let (left, right) = curve.split_at(2.0).unwrap();
+        check(&left, &clamped.split_at(2.0).unwrap().0);
+        check(&right, &clamped.split_at(2.0).unwrap().1);
+        assert_eq!(curve.to_bezier_segments().len(), 5);
+        assert_relative_eq!(
+            curve.arc_length(0.0, 5.0, 1e-10).unwrap(),
+            clamped.arc_length(0.0, 5.0, 1e-10).unwrap(),
+            epsilon = 1e-9
+        );
+        assert_eq!(
+            curve.insert_knot(-1.0, 1),
+            Err(CurveError::ParameterOutOfRange)
+        );
+    }
+
+    /// Möbius reparameterization clamps periodic curves, without changing
+    /// their shape.
+    #[test]
+    fn mobius_reparameterize_periodic() {
+        let curve = periodic_cubic();
+        let c = 2.0;
+        let reparameterized = curve.mobius_reparameterize(c).unwrap();
+        assert_eq!(reparameterized.kind(), CurveKind::Clamped);
+        assert_eq!(reparameterized.min_u(), 0.0);
+        assert_eq!(reparameterized.max_u(), 5.0);
+        for i in 0..51 {
+            let s = (i as f64) / 50.0;
+            let t = s / (s + c * (1.0 - s));
+            assert_relative_eq!(
+                reparameterized.de_boor(5.0 * s),
+                curve.de_boor(5.0 * t),
+                epsilon = 1e-9
+            );
+        }
     }
 }
@@ -0,0 +1,514 @@
+use super::banded::BandedMatrix;
+use super::interpolate::{
+    averaged_knots, basis_rows, matrix_to_points, parameterize, Parameterization,
+};
+use super::{Curve, CurveError, Result};
+use crate::algebra::{ScalarT, VectorT};
+use crate::knotvec::KnotVec;
+use nalgebra::DMatrix;
+use std::cmp::Ordering;
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Creates a curve which approximates a sequence of points, using
+    /// weighted least squares.
+    ///
+    /// The curve is a non-rational B-spline with the requested number of
+    /// control points, and a parameter range of `0..=1`. It passes exactly
+    /// through the first and last points, while the remaining control points
+    /// minimize the weighted sum of the squared distances between each point
+    /// and the curve at the parameter value of that point (section 9.4.1 of
+    /// The NURBS Book). The parameter values are chosen according to
+    /// `parameterization`, and the knots are placed so that every knot span
+    /// contains at least one parameter value (equation 9.69 of The NURBS
+    /// Book).
+    ///
+    /// Returns the curve along with the largest distance between any point
+    /// with a non-zero weight and the curve at the parameter value of that
+    /// point. An error is returned if any coordinate of a point is not
+    /// finite.
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the points to approximate, in order
+    /// * `point_weights` - the weight of each point in the least-squares sum
+    ///   (each must be non-negative), or `None` to weight all points equally
+    /// * `degree` - the degree of the curve
+    /// * `n_control_points` - the number of control points of the curve
+    ///   (must be greater than the degree, and no more than the number of
+    ///   points)
+    /// * `parameterization` - the method used to choose the parameter value of
+    ///   each point
+    pub fn approximate(
+        points: &[V],
+        point_weights: Option<&[N]>,
+        degree: usize,
+        n_control_points: usize,
+        parameterization: Parameterization,
+    ) -> Result<(Self, N)> {
+        if degree == 0 {
+            return Err(CurveError::InvalidDegree);
+        } else if n_control_points <= degree {
+            return Err(CurveError::InsufficientControlPoints {
+                degree,
+                number_supplied: n_control_points,
+            });
+        } else if points.len() < n_control_points {
+            return Err(CurveError::InsufficientDataPoints {
+                required: n_control_points,
+                number_supplied: points.len(),
+            });
+        }
+        let point_weights = check_points(points, point_weights)?;
+        let params = parameterize(points, parameterization)?;
+        let knots = approximation_knots(&params, degree, n_control_points);
+        let curve = least_squares(points, &point_weights, &params, knots, degree)?;
+        let deviations = deviations(&curve, points, &point_weights, &params);
+        let deviation = deviations.iter().fold(N::zero(), |a, &b| a.max(b));
+        Ok((curve, deviation))
+    }
+
+    /// Creates a curve which approximates a sequence of points to within a
+    /// tolerance, using as few control points as it can.
+    ///
+    /// The approximation starts from a single Bézier segment. While the
+    /// distance between any point and the curve (at the parameter value of
+    /// that point) exceeds `tolerance`, a knot is inserted into the knot
+    /// span containing the point with the largest distance, and the curve is
+    /// fitted again by weighted least squares (as for `approximate`). Each
+    /// knot is placed at the middle of the points inside its span, so that
+    /// it splits them evenly.
+    ///
+    /// Returns the curve along with the largest distance between any point
+    /// with a non-zero weight and the curve at the parameter value of that
+    /// point. An error is returned if the tolerance cannot be reached, which
+    /// can only happen when there are too few points inside the spans where
+    /// the distance is too large, or if any coordinate of a point is not
+    /// finite.
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the points to approximate, in order
+    /// * `point_weights` - the weight of each point in the least-squares sum
+    ///   (each must be non-negative), or `None` to weight all points equally
+    /// * `degree` - the degree of the curve
+    /// * `tolerance` - the maximum allowed distance from the points
+    /// * `parameterization` - the method used to choose the parameter value of
+    ///   each point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::{Curve, Parameterization};
+    /// use nalgebra::Vector2;
+    ///
+    /// let points: Vec<Vector2<f64>> = (0..100)
+    ///     .map(|i| {
+    ///         let x = (i as f64) / 10.0;
+    ///         Vector2::new(x, x.sin())
+    ///     })
+    ///     .collect();
+    /// let (curve, deviation) =
+    ///     Curve::approximate_within(&points, None, 3, 1e-4, Parameterization::ChordLength)
+    ///         .unwrap();
+    /// assert!(deviation <= 1e-4);
+    /// assert!(curve.control_points().len() < points.len() / 2);
+    /// ```
+    pub fn approximate_within(
+        points: &[V],
+        point_weights: Option<&[N]>,
+        degree: usize,
+        tolerance: N,
+        parameterization: Parameterization,
+    ) -> Result<(Self, N)> {
+        if degree == 0 {
+            return Err(CurveError::InvalidDegree);
+        } else if points.len() <= degree {
+            return Err(CurveError::InsufficientDataPoints {
+                required: degree + 1,
+                number_supplied: points.len(),
+            });
+        }
+        let point_weights = check_points(points, point_weights)?;
+        let params = parameterize(points, parameterization)?;
+        let mut knots = vec![N::zero(); degree + 1];
+        knots.extend(std::iter::repeat_n(N::one(), degree + 1));
+
+        loop {
+            let knot_vec = KnotVec::new(knots.clone()).expect("approximation knots must be valid");
+            let curve = least_squares(points, &point_weights, &params, knot_vec, degree)?;
+            let deviations = deviations(&curve, points, &point_weights, &params);
+            let deviation = deviations.iter().fold(N::zero(), |a, &b| a.max(b));
+            if deviation <= tolerance {
+                return Ok((curve, deviation));
+            }
+
+            // split the span containing the worst point which can be split
+            let mut worst: Vec<usize> = (0..points.len())
+                .filter(|&k| deviations[k] > tolerance)
+                .collect();
+            worst.sort_by(|&a, &b| {
+                deviations[b]
+                    .partial_cmp(&deviations[a])
+                    .unwrap_or(Ordering::Equal)
+            });
+            let new_knot = worst
+                .iter()
+                .find_map(|&k| split_span(&knots, &params, &point_weights, params[k]));
+            match new_knot {
+                Some(u) => {
+                    let i = knots.iter().rposition(|&k| k <= u).unwrap();
+                    knots.insert(i + 1, u);
+                }
+                None => return Err(CurveError::ApproximationToleranceExceeded),
+            }
+        }
+    }
+}
+
+/// Checks that the points are finite and that their weights are valid,
+/// returning a weight for every point.
+fn check_points<N, V>(points: &[V], point_weights: Option<&[N]>) -> Result<Vec<N>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    if !points
+        .iter()
+        .all(|p| (0..p.dimension()).all(|d| p[d].is_finite()))
+    {
+        return Err(CurveError::NonFinitePoint);
+    }
+    match point_weights {
+        None => Ok(vec![N::one(); points.len()]),
+        Some(w) if w.len() == points.len() && w.iter().all(|&w| w >= N::zero()) => Ok(w.to_vec()),
+        Some(_) => Err(CurveError::InvalidPointWeights),
+    }
+}
+
+/// Places the knots of an approximating curve, so that every knot span
+/// contains at least one parameter value (equation 9.69 of The NURBS Book).
+///
+/// With one control point for every point, the knots are averaged as for
+/// interpolation instead.
+fn approximation_knots<N: ScalarT>(
+    params: &[N],
+    degree: usize,
+    n_control_points: usize,
+) -> KnotVec<N> {
+    if n_control_points == params.len() {
+        return averaged_knots(params, degree);
+    }
+    let spans = n_control_points - degree;
+    let mut knots = vec![N::zero(); degree + 1];
+    // j * d = j * (m + 1) / (n - p) is split into its integer part i and its
+    // fractional part alpha
+    for j in 1..spans {
+        let i = j * params.len() / spans;
+        let alpha =
+            N::from_usize(j * params.len() - i * spans).unwrap() / N::from_usize(spans).unwrap();
+        knots.push((N::one() - alpha) * params[i - 1] + alpha * params[i]);
+    }
+    knots.extend(std::iter::repeat_n(N::one(), degree + 1));
+    KnotVec::new(knots).expect("approximation knots must be valid")
+}
+
+/// Fits a curve with a given knot vector to points by weighted least
+/// squares, keeping the first and last points fixed.
+fn least_squares<N, V>(
+    points: &[V],
+    point_weights: &[N],
+    params: &[N],
+    knots: KnotVec<N>,
+    degree: usize,
+) -> Result<Curve<N, V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let n = knots.len() - degree - 2;
+    let m = points.len() - 1;
+    let (first, last) = (points[0].clone(), points[m].clone());
+    let mut control_points = vec![first.clone()];
+
+    if n > 1 {
+        // normal equations for the interior control points, with the
+        // contributions of the fixed end control points removed. Each point
+        // only involves the basis functions which are non-zero at its
+        // parameter value, so the equations are banded.
+        let dimension = first.dimension();
+        let mut lhs = BandedMatrix::zeros(n - 1, degree, degree);
+        let mut rhs = DMatrix::zeros(n - 1, dimension);
+        let rows = basis_rows(&knots, degree, params);
+        for k in 1..m {
+            let (start, basis) = &rows[k];
+            let value = |i: usize| match i.checked_sub(*start) {
+                Some(j) if j < basis.len() => basis[j],
+                _ => N::zero(),
+            };
+            let residual = (points[k].clone() - first.clone() * value(0) - last.clone() * value(n))
+                * point_weights[k];
+            let interior = (*start..start + basis.len()).filter(|&i| i > 0 && i < n);
+            for a in interior.clone() {
+                for b in interior.clone() {
+                    lhs[(a - 1, b - 1)] += value(a) * value(b) * point_weights[k];
+                }
+                for d in 0..dimension {
+                    rhs[(a - 1, d)] += value(a) * residual[d];
+                }
+            }
+        }
+        let solution = lhs.solve(&rhs).ok_or(CurveError::SingularSystem)?;
+        control_points.extend(matrix_to_points::<N, V>(&solution));
+    }
+
+    control_points.push(last);
+    let weights = vec![N::one(); control_points.len()];
+    Curve::new(degree, control_points, weights, knots)
+}
+
+/// Returns the distance of each point from the curve, at the parameter
+/// value of the point, or zero for points whose weight is zero.
+fn deviations<N, V>(curve: &Curve<N, V>, points: &[V], point_weights: &[N], params: &[N]) -> Vec<N>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    points
+        .iter()
+        .zip(params)
+        .zip(point_weights)
+        .map(|((point, &u), &w)| {
+            if w > N::zero() {
+                (curve.de_boor(u) - point.clone()).norm()
+            } else {
+                N::zero()
+            }
+        })
+        .collect()
+}
+
+/// Chooses a knot which splits the knot span containing the parameter `u`,
+/// in the middle of the weighted points inside the span.
+///
+/// Returns `None` if there are fewer than two such points.
+fn split_span<N: ScalarT>(knots: &[N], params: &[N], point_weights: &[N], u: N) -> Option<N> {
+    let i = knots.iter().rposition(|&k| k <= u).unwrap();
+    let (a, b) = if i + 1 < knots.len() {
+        (knots[i], knots[i + 1])
+    } else {
+        // the last parameter value belongs to the last non-empty span
+        let j = knots.iter().rposition(|&k| k < u).unwrap();
+        (knots[j], u)
+    };
+    let inside: Vec<N> = params
+        .iter()
+        .zip(point_weights)
+        .filter(|&(&t, &w)| t > a && t < b && w > N::zero())
+        .map(|(&t, _)| t)
+        .collect();
+    if inside.len() < 2 {
+        return None;
+    }
+    let mid = inside.len() / 2;
+    Some((inside[mid - 1] + inside[mid]) / N::from_f64(2.0).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::Vector2;
+
+    /// Points sampled from a sine wave, with a small deterministic wobble.
+    fn noisy_sine() -> Vec<Vector2<f64>> {
+        (0..60)
+            .map(|i| {
+                let x = (i as f64) / 10.0;
+                let noise = 1e-3 * ((i * 7919 % 13) as f64 / 6.0 - 1.0);
+                Vector2::new(x, x.sin() + noise)
+            })
+            .collect()
+    }
+
+    /// Points sampled from a Bézier curve are reproduced exactly.
+    #[test]
+    fn approximate_bezier() {
+        let bezier = Curve::new(
+            3,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 2.0),
+                Vector2::new(3.0, -1.0),
+                Vector2::new(4.0, 1.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        let points: Vec<Vector2<f64>> = (0..=10).map(|i| bezier.de_boor(i as f64 / 10.0)).collect();
+        let (curve, deviation) =
+            Curve::approximate(&points, None, 3, 4, Parameterization::Uniform).unwrap();
+        assert_relative_eq!(deviation, 0.0, epsilon = 1e-12);
+        for (cp, expected) in curve.control_points().iter().zip(bezier.control_points()) {
+            assert_relative_eq!(cp, expected, epsilon = 1e-12);
+        }
+    }
+
+    /// Least-squares approximations keep their end points fixed, and report
+    /// their largest deviation.
+    #[test]
+    fn approximate() {
+        let points = noisy_sine();
+        let params = parameterize(&points, Parameterization::ChordLength).unwrap();
+        let mut previous = f64::INFINITY;
+        for &n_control_points in &[4, 6, 10, 20, 60] {
+            let (curve, deviation) = Curve::approximate(
+                &points,
+                None,
+                3,
+                n_control_points,
+                Parameterization::ChordLength,
+            )
+            .unwrap();
+            assert_eq!(curve.control_points().len(), n_control_points);
+            assert_relative_eq!(curve.de_boor(0.0), points[0]);
+            assert_relative_eq!(curve.de_boor(1.0), points[59]);
+            let max = points
+                .iter()
+                .zip(&params)
+                .map(|(p, &u)| (curve.de_boor(u) - p).norm())
+                .fold(0.0, f64::max);
+            assert_relative_eq!(deviation, max);
+            assert!(deviation < previous);
+            previous = deviation;
+        }
+        // with one control point per point, the curve interpolates
+        assert!(previous < 1e-9);
+    }
+
+    /// Point weights control how closely each point is approximated.
+    #[test]
+    fn approximate_weighted() {
+        let mut points = noisy_sine();
+        let mut weights = vec![1.0; points.len()];
+        weights[30] = 0.0;
+        let (curve, _) =
+            Curve::approximate(&points, Some(&weights), 3, 8, Parameterization::Uniform).unwrap();
+
+        // an outlier with zero weight is ignored
+        points[30].y += 5.0;
+        let (ignored, _) =
+            Curve::approximate(&points, Some(&weights), 3, 8, Parameterization::Uniform).unwrap();
+        for (a, b) in ignored.control_points().iter().zip(curve.control_points()) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+
+        // a heavily weighted point is approximated more closely
+        let mut deviation = |weight| {
+            weights[30] = weight;
+            let (curve, _) =
+                Curve::approximate(&points, Some(&weights), 3, 8, Parameterization::Uniform)
+                    .unwrap();
+            (curve.de_boor(30.0 / 59.0) - points[30]).norm()
+        };
+        let light = deviation(1.0);
+        let heavy = deviation(100.0);
+        assert!(heavy < light / 5.0);
+    }
+
+    /// The adaptive approximation adds control points until it is within
+    /// tolerance.
+    #[test]
+    fn approximate_within() {
+        let points = noisy_sine();
+        let mut previous = 0;
+        for &tolerance in &[1e-1, 1e-2, 2e-3] {
+            let (curve, deviation) = Curve::approximate_within(
+                &points,
+                None,
+                3,
+                tolerance,
+                Parameterization::ChordLength,
+            )
+            .unwrap();
+            assert!(deviation <= tolerance);
+            let n_control_points = curve.control_points().len();
+            assert!(n_control_points > previous);
+            assert!(n_control_points < points.len() / 2);
+            previous = n_control_points;
+        }
+
+        // a line needs only a single segment
+        let line: Vec<Vector2<f64>> = (0..10).map(|i| Vector2::new(i as f64, 2.0)).collect();
+        let (curve, deviation) =
+            Curve::approximate_within(&line, None, 2, 1e-6, Parameterization::ChordLength).unwrap();
+        assert_eq!(curve.control_points().len(), 3);
+        assert_relative_eq!(deviation, 0.0, epsilon = 1e-12);
+
+        // a zig-zag is only followed by interpolating it
+        let zigzag: Vec<Vector2<f64>> = (0..5)
+            .map(|i| Vector2::new(i as f64, (i % 2) as f64))
+            .collect();
+        let (curve, _) =
+            Curve::approximate_within(&zigzag, None, 3, 1e-3, Parameterization::ChordLength)
+                .unwrap();
+        assert_eq!(curve.control_points().len(), zigzag.len());
+    }
+
+    /// Invalid approximation problems.
+    #[test]
+    fn approximate_errors() {
+        let points = noisy_sine();
+        let uniform = Parameterization::Uniform;
+        assert_eq!(
+            Curve::approximate(&points, None, 0, 4, uniform),
+            Err(CurveError::InvalidDegree)
+        );
+        assert_eq!(
+            Curve::approximate(&points, None, 3, 3, uniform),
+            Err(CurveError::InsufficientControlPoints {
+                degree: 3,
+                number_supplied: 3
+            })
+        );
+        assert_eq!(
+            Curve::approximate(&points[..5], None, 3, 6, uniform),
+            Err(CurveError::InsufficientDataPoints {
+                required: 6,
+                number_supplied: 5
+            })
+        );
+        assert_eq!(
+            Curve::approximate_within(&points[..3], None, 3, 1e-3, uniform),
+            Err(CurveError::InsufficientDataPoints {
+                required: 4,
+                number_supplied: 3
+            })
+        );
+        assert_eq!(
+            Curve::approximate(&points, Some(&[1.0; 5]), 3, 6, uniform),
+            Err(CurveError::InvalidPointWeights)
+        );
+        let mut weights = vec![1.0; points.len()];
+        weights[3] = -1.0;
+        assert_eq!(
+            Curve::approximate_within(&points, Some(&weights), 3, 1e-3, uniform),
+            Err(CurveError::InvalidPointWeights)
+        );
+        let mut nan_points = points.clone();
+        nan_points[7].y = f64::NAN;
+        assert_eq!(
+            Curve::approximate_within(&nan_points, None, 3, 1e-3, uniform),
+            Err(CurveError::NonFinitePoint)
+        );
+        nan_points[7].y = f64::INFINITY;
+        assert_eq!(
+            Curve::approximate(&nan_points, None, 3, 6, uniform),
+            Err(CurveError::NonFinitePoint)
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use super::{Curve, CurveError, Result};
+use crate::algebra::{ScalarT, VectorT};
+
+/// Maximum depth of recursive subdivision in adaptive quadrature.
+const MAX_QUADRATURE_DEPTH: usize = 50;
+
+/// Maximum number of iterations when inverting the arc length function.
+const MAX_INVERSION_ITERATIONS: usize = 100;
+
+/// Relative accuracy of the lengths used by `Curve::param_at_length`.
+const DEFAULT_RELATIVE_TOLERANCE: f64 = 1e-10;
+
+/// Nodes and weights of 5-point Gauss-Legendre quadrature over `-1..=1`.
+pub(super) const GAUSS_LEGENDRE_5: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Computes the arc length of part of the curve.
+    ///
+    /// The length is found by integrating the speed of the curve, `|C'(u)|`,
+    /// using adaptive Gauss-Legendre quadrature. Each knot span is integrated
+    /// separately, so that the integrand is smooth over every interval of
+    /// integration.
+    ///
+    /// # Parameters
+    ///
+    /// * `u0` - the parameter value at the start of the arc
+    /// * `u1` - the parameter value at the end of the arc (must not be less
+    ///   than `u0`)
+    /// * `tolerance` - the required absolute accuracy of the length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::Curve;
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::Vector2;
+    ///
+    /// let line = Curve::new(
+    ///     1,
+    ///     vec![Vector2::new(0.0, 0.0), Vector2::new(3.0, 4.0)],
+    ///     vec![1.0, 1.0],
+    ///     KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let length = line.arc_length(0.0, 1.0, 1e-9).unwrap();
+    /// assert!((length - 5.0f64).abs() < 1e-9);
+    /// ```
+    pub fn arc_length(&self, u0: N, u1: N, tolerance: N) -> Result<N> {
+        if u0 > u1 {
+            return Err(CurveError::DegenerateParameterRange);
+        }
+        if u0 < self.min_u() || u1 > self.max_u() {
+            return Err(CurveError::ParameterOutOfRange);
+        }
+
+        let intervals = self.span_intervals(u0, u1);
+        let span_tolerance = tolerance / N::from_usize(intervals.len().max(1)).unwrap();
+        Ok(intervals.iter().fold(N::zero(), |length, &(a, b)| {
+            length + self.span_arc_length(a, b, span_tolerance)
+        }))
+    }
+
+    /// Finds the parameter value at a given arc length along the curve.
+    ///
+    /// This is the inverse of `arc_length`, measured from the start of the
+    /// curve: it returns the parameter `u` such that the length of the curve
+    /// between `min_u` and `u` is `s`. The length is found to a relative
+    /// accuracy of about `1e-10` (or a thousand times the machine epsilon of
+    /// `N`, if that is larger).
+    ///
+    /// Each call measures the whole curve. To sample many points, build an
+    /// `ArcLengthTable` once with `arc_length_table`, and use its
+    /// `param_at_length` method instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `s` - the arc length from the start of the curve (must be between
+    ///   zero and the total length of the curve)
+    pub fn param_at_length(&self, s: N) -> Result<N> {
+        let relative = N::from_f64(DEFAULT_RELATIVE_TOLERANCE)
+            .unwrap()
+            .max(N::default_epsilon() * N::from_f64(1e3).unwrap());
+        let polygon_length = self.control_points.windows(2).fold(N::zero(), |length, w| {
+            length + (w[1].clone() - w[0].clone()).norm()
+        });
+        self.arc_length_table(relative * polygon_length)
+            .param_at_length(s)
+    }
+
+    /// Measures the curve, returning a table which can quickly find the
+    /// parameter values at many arc lengths.
+    ///
+    /// The length of each knot span is found once, to the given tolerance,
+    /// so that each lookup in the table only needs to integrate within the
+    /// single span which contains the requested length.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the required absolute accuracy of the lengths
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::Curve;
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::Vector2;
+    ///
+    /// let parabola = Curve::new(
+    ///     2,
+    ///     vec![
+    ///         Vector2::new(0.0f64, 0.0),
+    ///         Vector2::new(1.0, 2.0),
+    ///         Vector2::new(2.0, 0.0),
+    ///     ],
+    ///     vec![1.0; 3],
+    ///     KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// // parameter values spaced evenly in distance along the curve
+    /// let table = parabola.arc_length_table(1e-10);
+    /// let n = 10;
+    /// for i in 0..=n {
+    ///     let s = table.length() * (i as f64) / (n as f64);
+    ///     let u = table.param_at_length(s).unwrap();
+    ///     assert!((parabola.arc_length(0.0, u, 1e-10).unwrap() - s).abs() < 1e-9);
+    /// }
+    /// ```
+    pub fn arc_length_table(&self, tolerance: N) -> ArcLengthTable<'_, N, V> {
+        let intervals = self.span_intervals(self.min_u(), self.max_u());
+        let span_tolerance = tolerance / N::from_usize(2 * intervals.len()).unwrap();
+        let mut cumulative = Vec::with_capacity(intervals.len());
+        let mut length = N::zero();
+        for &(a, b) in &intervals {
+            length += self.span_arc_length(a, b, span_tolerance);
+            cumulative.push(length);
+        }
+        ArcLengthTable {
+            curve: self,
+            intervals,
+            cumulative,
+            tolerance,
+            span_tolerance,
+        }
+    }
+
+    /// Returns the intervals of `u0..=u1` which lie within each non-empty
+    /// knot span.
+    fn span_intervals(&self, u0: N, u1: N) -> Vec<(N, N)> {
+        self.breakpoints()
+            .windows(2)
+            .filter(|w| w[1] > u0 && w[0] < u1)
+            .map(|w| (w[0].max(u0), w[1].min(u1)))
+            .collect()
+    }
+
+    /// Computes the arc length over an interval within a single knot span.
+    fn span_arc_length(&self, a: N, b: N, tolerance: N) -> N {
+        let whole = self.gauss_arc_length(a, b);
+        self.adaptive_arc_length(a, b, whole, tolerance, MAX_QUADRATURE_DEPTH)
+    }
+
+    /// Adaptively refines a Gauss-Legendre estimate of arc length, by
+    /// bisecting the interval until the estimates of the two halves agree
+    /// with the estimate of the whole.
+    fn adaptive_arc_length(&self, a: N, b: N, whole: N, tolerance: N, depth: usize) -> N {
+        let two = N::from_f64(2.0).unwrap();
+        let m = (a + b) / two;
+        let left = self.gauss_arc_length(a, m);
+        let right = self.gauss_arc_length(m, b);
+        if depth == 0 || (left + right - whole).abs() <= tolerance {
+            left + right
+        } else {
+            self.adaptive_arc_length(a, m, left, tolerance / two, depth - 1)
+                + self.adaptive_arc_length(m, b, right, tolerance / two, depth - 1)
+        }
+    }
+
+    /// Estimates the arc length over an interval using 5-point
+    /// Gauss-Legendre quadrature.
+    fn gauss_arc_length(&self, a: N, b: N) -> N {
+        let half_width = (b - a) / N::from_f64(2.0).unwrap();
+        let mid = a + half_width;
+        GAUSS_LEGENDRE_5.iter().fold(N::zero(), |sum, &(x, w)| {
+            let u = mid + half_width * N::from_f64(x).unwrap();
+            sum + N::from_f64(w).unwrap() * self.derivatives(u, 1)[1].norm()
+        }) * half_width
+    }
+}
+
+/// Cumulative arc lengths of the knot spans of a curve, for finding the
+/// parameter values at many arc lengths.
+///
+/// A table is created by `Curve::arc_length_table`, and borrows the curve it
+/// measures.
+#[derive(Clone, Debug)]
+pub struct ArcLengthTable<'a, N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    curve: &'a Curve<N, V>,
+    intervals: Vec<(N, N)>,
+    cumulative: Vec<N>,
+    tolerance: N,
+    span_tolerance: N,
+}
+
+impl<'a, N, V> ArcLengthTable<'a, N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Returns the total length of the curve.
+    pub fn length(&self) -> N {
+        self.cumulative.last().cloned().unwrap_or_else(N::zero)
+    }
+
+    /// Finds the parameter value at a given arc length along the curve.
+    ///
+    /// Returns the parameter `u` such that the length of the curve between
+    /// `min_u` and `u` is `s`, to the tolerance of the table. The knot span
+    /// containing `s` is found by binary search of the cumulative lengths,
+    /// and the parameter is then found within that span using Newton
+    /// iteration, safeguarded by bisection so that it also converges where
+    /// the speed of the curve is zero (for example, at cusps).
+    ///
+    /// # Parameters
+    ///
+    /// * `s` - the arc length from the start of the curve (must be between
+    ///   zero and the total length of the curve)
+    pub fn param_at_length(&self, s: N) -> Result<N> {
+        if s < N::zero() {
+            return Err(CurveError::ArcLengthOutOfRange);
+        } else if s > self.length() {
+            return if s <= self.length() + self.tolerance {
+                Ok(self.curve.max_u())
+            } else {
+                Err(CurveError::ArcLengthOutOfRange)
+            };
+        }
+
+        // find the knot span containing the requested length
+        let i = self.cumulative.partition_point(|&c| c < s);
+        let (a, b) = self.intervals[i];
+        let start = if i == 0 {
+            N::zero()
+        } else {
+            self.cumulative[i - 1]
+        };
+        let remaining = s - start;
+        let length = self.cumulative[i] - start;
+        if length <= N::zero() {
+            return Ok(a);
+        }
+
+        // Newton iteration on the length within the span, falling back to
+        // bisection whenever a Newton step leaves the bracketing interval
+        let half_tolerance = self.tolerance / N::from_f64(2.0).unwrap();
+        let (mut lo, mut hi) = (a, b);
+        let mut u = a + (b - a) * remaining / length;
+        for _ in 0..MAX_INVERSION_ITERATIONS {
+            let f = self.curve.span_arc_length(a, u, self.span_tolerance) - remaining;
+            if f.abs() <= half_tolerance {
+                break;
+            }
+            if f > N::zero() {
+                hi = u;
+            } else {
+                lo = u;
+            }
+            let speed = self.curve.derivatives(u, 1)[1].norm();
+            let newton = u - f / speed;
+            u = if speed > N::zero() && newton > lo && newton < hi {
+                newton
+            } else {
+                (lo + hi) / N::from_f64(2.0).unwrap()
+            };
+        }
+        Ok(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{reed_leaf, unit_circle};
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::f64::consts::PI;
+
+    /// The length of a circle, and of its quadrants.
+    #[test]
+    fn arc_length_circle() {
+        let circle = unit_circle();
+        assert_relative_eq!(
+            circle.arc_length(0.0, 1.0, 1e-10).unwrap(),
+            2.0 * PI,
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            circle.arc_length(0.25, 0.5, 1e-10).unwrap(),
+            PI / 2.0,
+            epsilon = 1e-10
+        );
+        assert_eq!(circle.arc_length(0.3, 0.3, 1e-10).unwrap(), 0.0);
+
+        assert_eq!(
+            circle.arc_length(0.5, 0.25, 1e-10),
+            Err(CurveError::DegenerateParameterRange)
+        );
+        assert_eq!(
+            circle.arc_length(0.0, 1.5, 1e-10),
+            Err(CurveError::ParameterOutOfRange)
+        );
+    }
+
+    /// The length of the reed leaf matches the length of a finely-sampled
+    /// polyline.
+    #[test]
+    fn arc_length_reed_leaf() {
+        let leaf = reed_leaf();
+        let n = 60000;
+        let polyline_length: f64 = (0..n)
+            .map(|i| {
+                let u0 = 6.0 * (i as f64) / (n as f64);
+                let u1 = 6.0 * ((i + 1) as f64) / (n as f64);
+                (leaf.de_boor(u1) - leaf.de_boor(u0)).norm()
+            })
+            .sum();
+        let length = leaf.arc_length(0.0, 6.0, 1e-8).unwrap();
+        assert_relative_eq!(length, polyline_length, epsilon = 1e-4);
+
+        // lengths of adjacent pieces add up
+        let a = leaf.arc_length(0.0, 2.7, 1e-9).unwrap();
+        let b = leaf.arc_length(2.7, 6.0, 1e-9).unwrap();
+        assert_relative_eq!(a + b, length, epsilon = 1e-7);
+    }
+
+    /// Points evenly spaced in arc length around a circle are evenly spaced
+    /// in angle.
+    #[test]
+    fn param_at_length_circle() {
+        let circle = unit_circle();
+        for i in 0..17 {
+            let angle = 2.0 * PI * (i as f64) / 16.0;
+            let u = circle.param_at_length(angle).unwrap();
+            let pt = circle.de_boor(u);
+            assert_relative_eq!(pt.x, angle.cos(), epsilon = 1e-9);
+            assert_relative_eq!(pt.y, angle.sin(), epsilon = 1e-9);
+        }
+
+        assert_eq!(
+            circle.param_at_length(-1.0),
+            Err(CurveError::ArcLengthOutOfRange)
+        );
+        assert_eq!(
+            circle.param_at_length(7.0),
+            Err(CurveError::ArcLengthOutOfRange)
+        );
+    }
+
+    /// Inverting arc length on a curve with cusps, where the speed of the
+    /// curve is zero.
+    #[test]
+    fn param_at_length_reed_leaf() {
+        let leaf = reed_leaf();
+        let table = leaf.arc_length_table(1e-8);
+        assert_relative_eq!(
+            table.length(),
+            leaf.arc_length(0.0, 6.0, 1e-9).unwrap(),
+            epsilon = 1e-7
+        );
+        for i in 0..41 {
+            let s = table.length() * (i as f64) / 40.0;
+            let u = table.param_at_length(s).unwrap();
+            assert_relative_eq!(leaf.arc_length(0.0, u, 1e-9).unwrap(), s, epsilon = 1e-7);
+            assert_relative_eq!(u, leaf.param_at_length(s).unwrap(), epsilon = 1e-6);
+        }
+        assert_eq!(table.param_at_length(0.0).unwrap(), 0.0);
+        assert_eq!(table.param_at_length(table.length()).unwrap(), 6.0);
+        assert_eq!(
+            table.param_at_length(table.length() + 1.0),
+            Err(CurveError::ArcLengthOutOfRange)
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::algebra::ScalarT;
+use nalgebra::DMatrix;
+use std::ops::{Index, IndexMut, Range};
+
+/// Square matrix whose non-zero elements lie in a band about the diagonal.
+///
+/// Row `i` may have non-zero elements in columns `i - lower ..= i + upper`.
+/// The matrices of B-spline fitting problems have this form, since each
+/// basis function is non-zero over only `degree + 1` knot spans, and so they
+/// can be stored and solved in time proportional to their size rather than
+/// its cube.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct BandedMatrix<N> {
+    size: usize,
+    lower: usize,
+    upper: usize,
+    /// Elements of each row, in columns `i - lower ..= i + upper + lower`.
+    /// The extra `lower` columns hold the elements filled in by row
+    /// interchanges during elimination.
+    elements: Vec<N>,
+}
+
+impl<N: ScalarT> BandedMatrix<N> {
+    /// Creates a matrix of zeros, with `lower` diagonals below the main
+    /// diagonal and `upper` diagonals above it.
+    pub(super) fn zeros(size: usize, lower: usize, upper: usize) -> Self {
+        BandedMatrix {
+            size,
+            lower,
+            upper,
+            elements: vec![N::zero(); size * (2 * lower + upper + 1)],
+        }
+    }
+
+    /// Creates a matrix from its rows, where each row is given by the column
+    /// of its first element and the elements from there onwards. The band is
+    /// made just wide enough to hold the rows.
+    pub(super) fn from_rows(rows: &[(usize, Vec<N>)]) -> Self {
+        let lower = rows
+            .iter()
+            .enumerate()
+            .map(|(i, (first, _))| i.saturating_sub(*first))
+            .max()
+            .unwrap_or(0);
+        let upper = rows
+            .iter()
+            .enumerate()
+            .map(|(i, (first, values))| (first + values.len()).saturating_sub(i + 1))
+            .max()
+            .unwrap_or(0);
+        let mut matrix = BandedMatrix::zeros(rows.len(), lower, upper);
+        for (i, (first, values)) in rows.iter().enumerate() {
+            for (j, &value) in values.iter().enumerate() {
+                matrix[(i, first + j)] = value;
+            }
+        }
+        matrix
+    }
+
+    /// Returns the number of rows (and columns) of the matrix.
+    pub(super) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the range of columns of row `i` which lie within the band.
+    pub(super) fn band(&self, i: usize) -> Range<usize> {
+        i.saturating_sub(self.lower)..(i + self.upper + 1).min(self.size)
+    }
+
+    /// Returns the offset into `elements` of element `(i, j)`, which may be
+    /// in the band or in the columns kept for fill-in.
+    fn offset(&self, i: usize, j: usize) -> usize {
+        let width = 2 * self.lower + self.upper + 1;
+        debug_assert!(j + self.lower >= i && j < i + self.upper + self.lower + 1);
+        i * width + j + self.lower - i
+    }
+
+    /// Solves the linear system `self * x = rhs`, by Gaussian elimination
+    /// with partial pivoting restricted to the band (algorithm 4.3.2 of
+    /// Golub and Van Loan's Matrix Computations).
+    ///
+    /// Returns `None` if the matrix is singular.
+    pub(super) fn solve(mut self, rhs: &DMatrix<N>) -> Option<DMatrix<N>> {
+        let n = self.size();
+        let mut x = rhs.clone();
+        // rows below the band of column k are zero in that column, and
+        // interchanges extend the rows above them by at most `lower` columns
+        let reach = self.lower + self.upper;
+        for k in 0..n {
+            let last_row = (k + self.lower).min(n - 1);
+            let last_column = (k + reach).min(n - 1);
+            let mut pivot = k;
+            for i in k + 1..=last_row {
+                if self.elements[self.offset(i, k)].abs()
+                    > self.elements[self.offset(pivot, k)].abs()
+                {
+                    pivot = i;
+                }
+            }
+            if self.elements[self.offset(pivot, k)] == N::zero() {
+                return None;
+            }
+            if pivot != k {
+                for j in k..=last_column {
+                    let (a, b) = (self.offset(k, j), self.offset(pivot, j));
+                    self.elements.swap(a, b);
+                }
+                x.swap_rows(k, pivot);
+            }
+
+            let diagonal = self.elements[self.offset(k, k)];
+            for i in k + 1..=last_row {
+                let factor = self.elements[self.offset(i, k)] / diagonal;
+                if factor == N::zero() {
+                    continue;
+                }
+                for j in k..=last_column {
+                    let a = self.elements[self.offset(k, j)];
+                    let offset = self.offset(i, j);
+                    self.elements[offset] -= factor * a;
+                }
+                for d in 0..x.ncols() {
+                    let a = x[(k, d)];
+                    x[(i, d)] -= factor * a;
+                }
+            }
+        }
+
+        // back substitution through the upper triangular factor
+        for k in (0..n).rev() {
+            let last_column = (k + reach).min(n - 1);
+            for d in 0..x.ncols() {
+                let mut sum = x[(k, d)];
+                for j in k + 1..=last_column {
+                    sum -= self.elements[self.offset(k, j)] * x[(j, d)];
+                }
+                x[(k, d)] = sum / self.elements[self.offset(k, k)];
+            }
+        }
+        Some(x)
+    }
+}
+
+impl<N: ScalarT> Index<(usize, usize)> for BandedMatrix<N> {
+    type Output = N;
+
+    /// Returns element `(i, j)`, which must lie within the band.
+    fn index(&self, (i, j): (usize, usize)) -> &N {
+        assert!(self.band(i).contains(&j), "element outside the band");
+        &self.elements[self.offset(i, j)]
+    }
+}
+
+impl<N: ScalarT> IndexMut<(usize, usize)> for BandedMatrix<N> {
+    /// Returns element `(i, j)`, which must lie within the band.
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut N {
+        assert!(self.band(i).contains(&j), "element outside the band");
+        let offset = self.offset(i, j);
+        &mut self.elements[offset]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Converts a banded matrix into a dense matrix.
+    fn to_dense(matrix: &BandedMatrix<f64>) -> DMatrix<f64> {
+        let n = matrix.size();
+        let mut dense = DMatrix::zeros(n, n);
+        for i in 0..n {
+            for j in matrix.band(i) {
+                dense[(i, j)] = matrix[(i, j)];
+            }
+        }
+        dense
+    }
+
+    /// Solutions of banded systems match the dense solver, including
+    /// systems which need row interchanges.
+    #[test]
+    fn solve() {
+        let n = 12;
+        let mut matrix = BandedMatrix::zeros(n, 2, 1);
+        for i in 0..n {
+            for j in matrix.band(i) {
+                matrix[(i, j)] = (1.3 * (i as f64) + 0.7 * (j as f64)).sin();
+            }
+        }
+        // a zero on the diagonal can only be eliminated by pivoting
+        matrix[(0, 0)] = 0.0;
+        let rhs = DMatrix::from_fn(n, 2, |i, d| (i as f64) - 3.0 * (d as f64));
+
+        let dense = to_dense(&matrix);
+        let expected = dense.clone().lu().solve(&rhs).unwrap();
+        let solution = matrix.solve(&rhs).unwrap();
+        assert_relative_eq!(solution, expected, epsilon = 1e-9);
+        assert_relative_eq!(dense * solution, rhs, epsilon = 1e-9);
+    }
+
+    /// The band of a matrix created from rows just holds the rows, and
+    /// singular matrices have no solution.
+    #[test]
+    fn from_rows() {
+        let rows = vec![
+            (0, vec![1.0, 0.0]),
+            (0, vec![0.5, 0.5]),
+            (1, vec![0.25, 0.5, 0.25]),
+            (2, vec![1.0]),
+        ];
+        let matrix = BandedMatrix::from_rows(&rows);
+        assert_eq!((matrix.lower, matrix.upper), (1, 1));
+        assert_eq!(matrix[(2, 3)], 0.25);
+        assert_eq!(matrix.band(3), 2..4);
+
+        let rhs = DMatrix::from_element(4, 1, 1.0);
+        let mut singular = matrix.clone();
+        singular[(3, 2)] = 0.0;
+        singular[(3, 3)] = 0.0;
+        assert_eq!(singular.solve(&rhs), None);
+        assert!(matrix.solve(&rhs).is_some());
+    }
+}
@@ -0,0 +1,231 @@
+use super::binomial;
+use crate::algebra::ScalarT;
+
+/// Maximum depth of recursive subdivision when isolating roots.
+const MAX_SUBDIVISION_DEPTH: usize = 64;
+
+/// Maximum number of iterations when finding a root of a polynomial.
+const MAX_ROOT_ITERATIONS: usize = 200;
+
+/// Finds the roots in `0..=1` of a polynomial in Bernstein form.
+///
+/// The polynomial is subdivided until each piece has at most one sign change
+/// in its coefficients, and therefore (by the variation-diminishing
+/// property) at most one root. Roots where the polynomial touches zero
+/// without changing sign are only found if they are exact.
+///
+/// # Parameters
+///
+/// * `coeffs` - the Bernstein coefficients of the polynomial
+/// * `tolerance` - the accuracy required of the roots (zero to find them as
+///   accurately as possible)
+pub(super) fn bernstein_roots<N: ScalarT>(coeffs: &[N], tolerance: N) -> Vec<N> {
+    let mut roots = Vec::new();
+    if coeffs.is_empty() {
+        return roots;
+    }
+    if coeffs[0] == N::zero() {
+        roots.push(N::zero());
+    }
+    bernstein_roots_in(coeffs, N::zero(), N::one(), tolerance, 0, false, &mut roots);
+    if coeffs[coeffs.len() - 1] == N::zero() {
+        roots.push(N::one());
+    }
+    roots
+}
+
+/// Finds the points in `0..1` (excluding the ends) where a polynomial in
+/// Bernstein form changes sign.
+///
+/// Unlike `bernstein_roots`, zeros where the polynomial touches zero without
+/// changing sign are not reported, even if they are exact, and neither are
+/// zeros at the ends of the interval. Exact zeros at the ends are divided
+/// out first, since they do not affect the sign of the polynomial inside the
+/// interval.
+///
+/// # Parameters
+///
+/// * `coeffs` - the Bernstein coefficients of the polynomial
+/// * `tolerance` - the accuracy required of the roots (zero to find them as
+///   accurately as possible)
+pub(super) fn bernstein_sign_changes<N: ScalarT>(coeffs: &[N], tolerance: N) -> Vec<N> {
+    let mut roots = Vec::new();
+    let coeffs = deflate_end_zeros(coeffs);
+    bernstein_roots_in(&coeffs, N::zero(), N::one(), tolerance, 0, true, &mut roots);
+    roots
+}
+
+/// Divides the factors `t` and `1 - t` out of a polynomial in Bernstein
+/// form, for as many times as its coefficients at each end are zero.
+///
+/// If `p(t) = t^k q(t)`, the coefficients of `q` (of degree `n - k`) are
+/// `c[i + k] * C(n, i + k) / C(n - k, i)`, and similarly at the other end.
+/// The result has the same sign as the polynomial inside the interval.
+fn deflate_end_zeros<N: ScalarT>(coeffs: &[N]) -> Vec<N> {
+    let leading = coeffs.iter().take_while(|&&c| c == N::zero()).count();
+    if leading == coeffs.len() {
+        return Vec::new();
+    }
+    let trailing = coeffs.iter().rev().take_while(|&&c| c == N::zero()).count();
+    let n = coeffs.len() - 1;
+    let m = n - leading - trailing;
+    (0..=m)
+        .map(|i| {
+            let j = i + leading;
+            coeffs[j] * binomial::<N>(n, j) / binomial::<N>(m, i)
+        })
+        .collect()
+}
+
+/// Finds the roots of a polynomial in Bernstein form over the interval
+/// `t0..t1` (excluding the ends), by recursive subdivision.
+///
+/// If `crossings_only` is set, exact zeros found where the interval is
+/// subdivided are only reported if the polynomial changes sign there.
+fn bernstein_roots_in<N: ScalarT>(
+    coeffs: &[N],
+    t0: N,
+    t1: N,
+    tolerance: N,
+    depth: usize,
+    crossings_only: bool,
+    roots: &mut Vec<N>,
+) {
+    let n = coeffs.len();
+    if n == 0 {
+        return;
+    }
+
+    let mut sign_changes = 0;
+    let mut last_sign = N::zero();
+    for &c in coeffs {
+        if c != N::zero() {
+            let sign = c.signum();
+            if last_sign != N::zero() && sign != last_sign {
+                sign_changes += 1;
+            }
+            last_sign = sign;
+        }
+    }
+    if sign_changes == 0 {
+        return;
+    }
+
+    let ends_differ = coeffs[0] * coeffs[n - 1] < N::zero();
+    if sign_changes == 1 && ends_differ || depth >= MAX_SUBDIVISION_DEPTH {
+        if ends_differ {
+            let t = bernstein_bracketed_root(coeffs, tolerance / (t1 - t0));
+            roots.push(t0 + (t1 - t0) * t);
+        }
+        return;
+    }
+
+    let half = N::from_f64(0.5).unwrap();
+    let (left, right) = bernstein_split(coeffs, half);
+    let tm = (t0 + t1) * half;
+    bernstein_roots_in(&left, t0, tm, tolerance, depth + 1, crossings_only, roots);
+    if right[0] == N::zero() {
+        // the signs of the polynomial just before and just after the middle
+        let before = nonzero_sign(left.iter().rev());
+        let after = nonzero_sign(right.iter());
+        if !crossings_only || before * after < N::zero() {
+            roots.push(tm);
+        }
+    }
+    bernstein_roots_in(&right, tm, t1, tolerance, depth + 1, crossings_only, roots);
+}
+
+/// Returns the sign of the first non-zero coefficient, or zero if they are
+/// all zero.
+fn nonzero_sign<'a, N: ScalarT>(mut coeffs: impl Iterator<Item = &'a N>) -> N {
+    coeffs
+        .find(|&&c| c != N::zero())
+        .map_or(N::zero(), |c| c.signum())
+}
+
+/// Finds the single root in `0..=1` of a polynomial in Bernstein form whose
+/// end coefficients have opposite signs, using Newton iteration safeguarded
+/// by bisection, to within `tolerance`.
+fn bernstein_bracketed_root<N: ScalarT>(coeffs: &[N], tolerance: N) -> N {
+    let half = N::from_f64(0.5).unwrap();
+    let derivative: Vec<N> = coeffs.windows(2).map(|w| w[1] - w[0]).collect();
+    let degree = N::from_usize(coeffs.len() - 1).unwrap();
+    let rising = coeffs[0] < N::zero();
+
+    let (mut lo, mut hi) = (N::zero(), N::one());
+    let mut t = coeffs[0] / (coeffs[0] - coeffs[coeffs.len() - 1]);
+    for _ in 0..MAX_ROOT_ITERATIONS {
+        let value = bernstein_eval(coeffs, t);
+        if value == N::zero() {
+            break;
+        }
+        if (value > N::zero()) == rising {
+            hi = t;
+        } else {
+            lo = t;
+        }
+        let slope = bernstein_eval(&derivative, t) * degree;
+        let newton = t - value / slope;
+        let next = if slope != N::zero() && newton > lo && newton < hi {
+            newton
+        } else {
+            (lo + hi) * half
+        };
+        if next == t || (next - t).abs() <= tolerance || hi - lo <= tolerance {
+            t = next;
+            break;
+        }
+        t = next;
+    }
+    t
+}
+
+/// Evaluates a polynomial in Bernstein form using de Casteljau's algorithm.
+pub(super) fn bernstein_eval<N: ScalarT>(coeffs: &[N], t: N) -> N {
+    if coeffs.is_empty() {
+        return N::zero();
+    }
+    let mut c = coeffs.to_vec();
+    for k in 1..c.len() {
+        for i in 0..c.len() - k {
+            c[i] = c[i] * (N::one() - t) + c[i + 1] * t;
+        }
+    }
+    c[0]
+}
+
+/// Splits a polynomial in Bernstein form at `t`, returning the coefficients
+/// of the two pieces (each re-parameterized over `0..=1`).
+pub(super) fn bernstein_split<N: ScalarT>(coeffs: &[N], t: N) -> (Vec<N>, Vec<N>) {
+    let n = coeffs.len();
+    let mut c = coeffs.to_vec();
+    let mut left = Vec::with_capacity(n);
+    let mut right = Vec::with_capacity(n);
+    left.push(c[0]);
+    right.push(c[n - 1]);
+    for k in 1..n {
+        for i in 0..n - k {
+            c[i] = c[i] * (N::one() - t) + c[i + 1] * t;
+        }
+        left.push(c[0]);
+        right.push(c[n - 1 - k]);
+    }
+    right.reverse();
+    (left, right)
+}
+
+/// Multiplies two polynomials in Bernstein form, returning the Bernstein
+/// coefficients of the product.
+pub(super) fn bernstein_product<N: ScalarT>(f: &[N], g: &[N]) -> Vec<N> {
+    let (m, n) = (f.len() - 1, g.len() - 1);
+    let mut product = vec![N::zero(); m + n + 1];
+    for (i, &a) in f.iter().enumerate() {
+        for (j, &b) in g.iter().enumerate() {
+            product[i + j] += binomial::<N>(m, i) * binomial::<N>(n, j) * a * b;
+        }
+    }
+    for (k, c) in product.iter_mut().enumerate() {
+        *c /= binomial::<N>(m + n, k);
+    }
+    product
+}
@@ -0,0 +1,157 @@
+use super::bernstein::{bernstein_product, bernstein_roots};
+use super::Curve;
+use crate::algebra::{ScalarT, VectorT};
+use crate::bounds::BoundingBox;
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Returns the bounding box of the control points of the curve.
+    ///
+    /// Since the weights of the curve are positive, the curve lies within the
+    /// convex hull of its control points, and so also within this box. The
+    /// box is cheap to compute, but may be considerably larger than the
+    /// curve.
+    pub fn control_bounding_box(&self) -> BoundingBox<V> {
+        BoundingBox::from_points(self.control_points.iter())
+    }
+
+    /// Returns a bounding box which fits the curve tightly.
+    ///
+    /// The extreme values of each component of the curve occur either at the
+    /// ends of a Bézier segment (over the parameter range of the curve) or
+    /// where the derivative of that component is zero. For a rational
+    /// segment `x(t) = X(t) / W(t)`, the zeros of the derivative are the
+    /// roots of the polynomial `X'(t) W(t) - X(t) W'(t)`, which are found in
+    /// Bernstein form.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the maximum distance between each face of the box and
+    ///   the true extent of the curve
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use capstan::curve::Curve;
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::Vector2;
+    ///
+    /// let parabola = Curve::new(
+    ///     2,
+    ///     vec![
+    ///         Vector2::new(0.0, 0.0),
+    ///         Vector2::new(1.0, 2.0),
+    ///         Vector2::new(2.0, 0.0),
+    ///     ],
+    ///     vec![1.0, 1.0, 1.0],
+    ///     KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let bounds = parabola.tight_bounding_box(1e-9);
+    /// assert!((bounds.max().y - 1.0f64).abs() < 1e-9);
+    /// assert_eq!(parabola.control_bounding_box().max().y, 2.0);
+    /// ```
+    pub fn tight_bounding_box(&self, tolerance: N) -> BoundingBox<V> {
+        let p = N::from_usize(self.degree).unwrap();
+        // the first control point is only on the curve if it is clamped
+        let start = self.de_boor(self.min_u());
+        let mut bounds = BoundingBox::from_points(std::iter::once(&start));
+        let breakpoints = self.breakpoints();
+        for (segment, span) in self
+            .bezier_decomposition()
+            .iter()
+            .zip(breakpoints.windows(2))
+        {
+            let (a, b) = (span[0], span[1]);
+            bounds.include(&segment[segment.len() - 1].to_cartesian());
+
+            // a bound on the speed of the segment converts the tolerance into
+            // a tolerance on its parameter
+            let weights: Vec<N> = segment.iter().map(|h| h.weight).collect();
+            let w_min = weights.iter().fold(N::max_value(), |m, &w| m.min(w));
+            let w_max = weights.iter().fold(N::zero(), |m, &w| m.max(w));
+            let points: Vec<V> = segment.iter().map(|h| h.to_cartesian()).collect();
+            let max_step = points.windows(2).fold(N::zero(), |m, w| {
+                m.max((w[1].clone() - w[0].clone()).norm())
+            });
+            if max_step == N::zero() {
+                continue;
+            }
+            let t_tolerance = tolerance / (p * (w_max / w_min) * (w_max / w_min) * max_step);
+
+            let weight_derivative: Vec<N> = weights.windows(2).map(|w| w[1] - w[0]).collect();
+            for i in 0..points[0].dimension() {
+                let x: Vec<N> = segment.iter().map(|h| h.point[i]).collect();
+                let x_derivative: Vec<N> = x.windows(2).map(|w| w[1] - w[0]).collect();
+                let numerator: Vec<N> = bernstein_product(&x_derivative, &weights)
+                    .into_iter()
+                    .zip(bernstein_product(&x, &weight_derivative))
+                    .map(|(l, r)| l - r)
+                    .collect();
+                for t in bernstein_roots(&numerator, t_tolerance) {
+                    bounds.include(&self.de_boor(a + (b - a) * t));
+                }
+            }
+        }
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{periodic_cubic, reed_leaf, unit_circle};
+    use approx::assert_relative_eq;
+    use nalgebra::Vector2;
+
+    /// The tight bounds of a circle are a square around it.
+    #[test]
+    fn tight_bounding_box_circle() {
+        let circle = unit_circle().subcurve(0.1, 0.9).unwrap();
+        let bounds = circle.tight_bounding_box(1e-12);
+        assert_relative_eq!(bounds.min(), &Vector2::new(-1.0, -1.0), epsilon = 1e-12);
+        assert_relative_eq!(bounds.max().y, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(bounds.max().x, circle.de_boor(0.1).x, epsilon = 1e-12);
+
+        let control = circle.control_bounding_box();
+        assert!(control.contains(bounds.min()) && control.contains(bounds.max()));
+    }
+
+    /// Tight bounds of the reed leaf match the extent of densely-sampled
+    /// points on the curve.
+    #[test]
+    fn tight_bounding_box_reed_leaf() {
+        let leaf = reed_leaf();
+        let bounds = leaf.tight_bounding_box(1e-9);
+        let n = 60000;
+        let samples: Vec<Vector2<f64>> = (0..n + 1)
+            .map(|i| leaf.de_boor(6.0 * (i as f64) / (n as f64)))
+            .collect();
+        for sample in &samples {
+            assert!((sample - bounds.min()).min() >= -1e-9);
+            assert!((bounds.max() - sample).max() >= -1e-9);
+        }
+        let sampled = crate::bounds::BoundingBox::from_points(samples.iter());
+        assert_relative_eq!(bounds.min(), sampled.min(), epsilon = 1e-6);
+        assert_relative_eq!(bounds.max(), sampled.max(), epsilon = 1e-6);
+    }
+
+    /// Tight bounds of a periodic curve, whose first control point does not
+    /// lie on the curve, match the extent of densely-sampled points.
+    #[test]
+    fn tight_bounding_box_periodic() {
+        let curve = periodic_cubic();
+        let bounds = curve.tight_bounding_box(1e-9);
+        let n = 50000;
+        let samples: Vec<Vector2<f64>> = (0..n + 1)
+            .map(|i| curve.de_boor(5.0 * (i as f64) / (n as f64)))
+            .collect();
+        let sampled = crate::bounds::BoundingBox::from_points(samples.iter());
+        assert!(bounds.min().x > 0.1);
+        assert_relative_eq!(bounds.min(), sampled.min(), epsilon = 1e-6);
+        assert_relative_eq!(bounds.max(), sampled.max(), epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,240 @@
+use super::Curve;
+use crate::algebra::{ScalarT, VectorT};
+
+/// Number of samples per knot span, per degree of the curve, used to seed
+/// the search for the closest point.
+const SAMPLES_PER_DEGREE: usize = 4;
+
+/// Maximum number of Newton iterations when refining a closest point.
+const MAX_NEWTON_ITERATIONS: usize = 50;
+
+/// Maximum number of step halvings when a Newton step does not reduce the
+/// distance to the point.
+const MAX_STEP_HALVINGS: usize = 30;
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Finds the point on the curve which is closest to a given point.
+    ///
+    /// Returns a tuple containing the parameter value of the closest point,
+    /// the closest point itself, and its distance from `point`.
+    ///
+    /// The curve is first sampled at several parameter values in every knot
+    /// span. Each local minimum of the sampled distance is then refined by
+    /// Newton iteration, which solves `C'(u) · (C(u) - point) = 0` using the
+    /// first and second derivatives of the curve (section 6.1 of The NURBS
+    /// Book). Newton steps which do not reduce the distance are shortened, so
+    /// that the iteration remains stable near cusps, where the derivative of
+    /// the curve vanishes. The ends of the curve are also considered as
+    /// candidates.
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - the point to project onto the curve
+    /// * `tolerance` - the iteration stops when the component of
+    ///   `C(u) - point` along the tangent of the curve, or the distance moved
+    ///   along the curve in one step, is less than this value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::Curve;
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::Vector2;
+    ///
+    /// let line = Curve::new(
+    ///     1,
+    ///     vec![Vector2::new(0.0, 0.0), Vector2::new(4.0, 0.0)],
+    ///     vec![1.0, 1.0],
+    ///     KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let (u, closest, distance) = line.closest_point(&Vector2::new(1.0, 2.0), 1e-9);
+    /// assert!((u - 0.25f64).abs() < 1e-9);
+    /// assert!((closest - Vector2::new(1.0, 0.0)).norm() < 1e-9);
+    /// assert!((distance - 2.0).abs() < 1e-9);
+    /// ```
+    pub fn closest_point(&self, point: &V, tolerance: N) -> (N, V, N) {
+        let samples = self.closest_point_samples(point);
+
+        // refine every local minimum of the sampled distance; the true
+        // minimum may lie on either side of the sample, possibly across a
+        // knot where the curve is not smooth, so the iteration is also
+        // started from the middle of each neighbouring interval
+        let mut best = samples[0].clone();
+        for (i, sample) in samples.iter().enumerate() {
+            let is_local_minimum = (i == 0 || samples[i - 1].2 >= sample.2)
+                && (i + 1 == samples.len() || samples[i + 1].2 >= sample.2);
+            if !is_local_minimum {
+                continue;
+            }
+            let mut starts = vec![sample.clone()];
+            if i > 0 {
+                starts.push(self.closest_point_sample(point, samples[i - 1].0, sample.0, 1, 2));
+            }
+            if i + 1 < samples.len() {
+                starts.push(self.closest_point_sample(point, sample.0, samples[i + 1].0, 1, 2));
+            }
+            for start in starts {
+                let refined = self.refine_closest_point(point, start, tolerance);
+                if refined.2 < best.2 {
+                    best = refined;
+                }
+            }
+        }
+        best
+    }
+
+    /// Samples the curve evenly within each knot span, returning the
+    /// parameter, point and distance from `point` of every sample.
+    fn closest_point_samples(&self, point: &V) -> Vec<(N, V, N)> {
+        let n = SAMPLES_PER_DEGREE * self.degree;
+        let mut samples = Vec::new();
+        for w in self.breakpoints().windows(2) {
+            let (a, b) = (w[0], w[1]);
+            let first = if samples.is_empty() { 0 } else { 1 };
+            for j in first..n + 1 {
+                samples.push(self.closest_point_sample(point, a, b, j, n));
+            }
+        }
+        samples
+    }
+
+    /// Evaluates the curve at parameter `a + (b - a) * j / n`, returning the
+    /// parameter, the point and its distance from `point`.
+    fn closest_point_sample(&self, point: &V, a: N, b: N, j: usize, n: usize) -> (N, V, N) {
+        let u = a + (b - a) * N::from_usize(j).unwrap() / N::from_usize(n).unwrap();
+        let pt = self.de_boor(u);
+        let distance = (pt.clone() - point.clone()).norm();
+        (u, pt, distance)
+    }
+
+    /// Refines an estimate of the closest point on the curve using Newton
+    /// iteration.
+    fn refine_closest_point(&self, point: &V, start: (N, V, N), tolerance: N) -> (N, V, N) {
+        let (min_u, max_u) = (self.min_u(), self.max_u());
+        let half = N::from_f64(0.5).unwrap();
+
+        let (mut u, mut pt, mut distance) = start;
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            let ders = self.derivatives(u, 2);
+            let diff = ders[0].clone() - point.clone();
+            let f = ders[1].dot(&diff);
+            let df = ders[2].dot(&diff) + ders[1].dot(&ders[1]);
+            let speed = ders[1].norm();
+
+            // zero cosine: the point lies in the normal plane of the curve
+            if f.abs() <= tolerance * speed {
+                break;
+            }
+
+            // take a Newton step where the distance function is convex, and
+            // otherwise a gradient descent step of a similar size
+            let mut step = if df > N::zero() {
+                -f / df
+            } else if speed > N::zero() {
+                -f.signum() * distance / speed
+            } else {
+                break;
+            };
+
+            // shorten the step until it reduces the distance
+            let mut next = None;
+            for _ in 0..MAX_STEP_HALVINGS {
+                let new_u = (u + step).max(min_u).min(max_u);
+                let new_pt = self.de_boor(new_u);
+                let new_distance = (new_pt.clone() - point.clone()).norm();
+                if new_distance <= distance {
+                    next = Some((new_u, new_pt, new_distance));
+                    break;
+                }
+                step *= half;
+            }
+            let (new_u, new_pt, new_distance) = match next {
+                Some(next) => next,
+                None => break,
+            };
+
+            let moved = (new_pt.clone() - pt.clone()).norm();
+            let stalled = new_u == u;
+            u = new_u;
+            pt = new_pt;
+            distance = new_distance;
+            if stalled || moved <= tolerance {
+                break;
+            }
+        }
+        (u, pt, distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{reed_leaf, unit_circle};
+    use approx::assert_relative_eq;
+    use nalgebra::Vector2;
+    use std::f64::consts::SQRT_2;
+
+    /// Projecting points onto a circle.
+    #[test]
+    fn closest_point_circle() {
+        let circle = unit_circle();
+
+        let (u, pt, distance) = circle.closest_point(&Vector2::new(2.0, 2.0), 1e-12);
+        assert_relative_eq!(pt, Vector2::new(SQRT_2 / 2.0, SQRT_2 / 2.0), epsilon = 1e-9);
+        assert_relative_eq!(distance, 2.0 * SQRT_2 - 1.0, epsilon = 1e-9);
+        assert_relative_eq!(circle.de_boor(u), pt, epsilon = 1e-12);
+
+        // points inside the circle, and across the seam of the circle
+        for &angle in &[0.0, 0.3, 1.7, 3.0, 4.5, 6.2] {
+            let target = Vector2::new(f64::cos(angle), f64::sin(angle)) * 0.4;
+            let (_, pt, distance) = circle.closest_point(&target, 1e-12);
+            assert_relative_eq!(pt, target / 0.4, epsilon = 1e-9);
+            assert_relative_eq!(distance, 0.6, epsilon = 1e-9);
+        }
+    }
+
+    /// Projecting points onto the reed leaf, which has several knot spans and
+    /// cusps, agrees with a brute-force search.
+    #[test]
+    fn closest_point_reed_leaf() {
+        let leaf = reed_leaf();
+        let n = 12000;
+        let dense: Vec<Vector2<f64>> = (0..n + 1)
+            .map(|i| leaf.de_boor(6.0 * (i as f64) / (n as f64)))
+            .collect();
+
+        for i in 0..12 {
+            for j in 0..16 {
+                let target = Vector2::new(80.0 + 10.0 * (i as f64), 0.0 + 20.0 * (j as f64));
+                let (u, pt, distance) = leaf.closest_point(&target, 1e-10);
+                assert_relative_eq!(leaf.de_boor(u), pt, epsilon = 1e-12);
+                assert_relative_eq!((pt - target).norm(), distance, epsilon = 1e-12);
+
+                let brute_force = dense
+                    .iter()
+                    .map(|p| (p - target).norm())
+                    .fold(f64::INFINITY, f64::min);
+                assert!(distance <= brute_force + 1e-9);
+                assert!(distance >= brute_force - 1e-2);
+            }
+        }
+    }
+
+    /// Points on the curve, including at cusps, are their own closest
+    /// points.
+    #[test]
+    fn closest_point_on_curve() {
+        let leaf = reed_leaf();
+        for &u in &[0.0, 0.7, 1.0, 2.0, 2.5, 3.9, 5.0, 6.0] {
+            let target = leaf.de_boor(u);
+            let (_, pt, distance) = leaf.closest_point(&target, 1e-10);
+            assert_relative_eq!(pt, target, epsilon = 1e-6);
+            assert!(distance < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,646 @@
+use super::bernstein::{bernstein_product, bernstein_sign_changes};
+use super::Curve;
+use crate::algebra::{ScalarT, VectorT};
+use nalgebra::{Vector2, Vector3};
+
+/// Number of samples per Bézier segment, per degree of the curve, used to
+/// isolate extrema of curvature.
+const SAMPLES_PER_DEGREE: usize = 8;
+
+/// Relative size below which the rate of change of curvature is treated as
+/// zero.
+const RATE_ROUNDING: f64 = 1e-10;
+
+/// Maximum number of bisection steps when refining an extremum of
+/// curvature.
+const MAX_BISECTIONS: usize = 100;
+
+/// Orthonormal frame at a point on a 3D curve.
+///
+/// The tangent is the unit tangent of the curve, and the normal and binormal
+/// complete a right-handed frame: `binormal = tangent × normal`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame<N: ScalarT> {
+    origin: Vector3<N>,
+    tangent: Vector3<N>,
+    normal: Vector3<N>,
+    binormal: Vector3<N>,
+}
+
+impl<N: ScalarT> Frame<N> {
+    /// Returns the point on the curve at which the frame is located.
+    pub fn origin(&self) -> &Vector3<N> {
+        &self.origin
+    }
+
+    /// Returns the unit tangent of the curve.
+    pub fn tangent(&self) -> &Vector3<N> {
+        &self.tangent
+    }
+
+    /// Returns the unit normal of the frame.
+    pub fn normal(&self) -> &Vector3<N> {
+        &self.normal
+    }
+
+    /// Returns the unit binormal of the frame.
+    pub fn binormal(&self) -> &Vector3<N> {
+        &self.binormal
+    }
+}
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Computes the curvature of the curve at parameter value `u`.
+    ///
+    /// The curvature is the reciprocal of the radius of the osculating circle,
+    /// and is always non-negative. It is computed from the first and second
+    /// derivatives of the curve, in any number of dimensions, as:
+    ///
+    /// ```text
+    /// κ = sqrt(|C'|² |C''|² - (C' · C'')²) / |C'|³
+    /// ```
+    ///
+    /// Where the first derivative of the curve is zero (for example, at a
+    /// cusp), the curvature is undefined and zero is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the parameter value at which to compute the curvature
+    pub fn curvature(&self, u: N) -> N {
+        let ders = self.derivatives(u, 2);
+        let speed_squared = ders[1].dot(&ders[1]);
+        if speed_squared == N::zero() {
+            return N::zero();
+        }
+        let d12 = ders[1].dot(&ders[2]);
+        let area_squared = (speed_squared * ders[2].dot(&ders[2]) - d12 * d12).max(N::zero());
+        area_squared.sqrt() / (speed_squared * speed_squared.sqrt())
+    }
+}
+
+impl<N> Curve<N, Vector3<N>>
+where
+    N: 'static + ScalarT,
+{
+    /// Computes the torsion of a 3D curve at parameter value `u`.
+    ///
+    /// The torsion measures how quickly the curve twists out of its
+    /// osculating plane. It is computed from the first three derivatives of
+    /// the curve as:
+    ///
+    /// ```text
+    /// τ = (C' × C'') · C''' / |C' × C''|²
+    /// ```
+    ///
+    /// Where the curvature is zero (for example, along a straight section),
+    /// the torsion is undefined and zero is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the parameter value at which to compute the torsion
+    pub fn torsion(&self, u: N) -> N {
+        let ders = self.derivatives(u, 3);
+        let cross = ders[1].cross(&ders[2]);
+        let cross_squared = cross.norm_squared();
+        if cross_squared == N::zero() {
+            return N::zero();
+        }
+        cross.dot(&ders[3]) / cross_squared
+    }
+
+    /// Computes the Frenet frame of a 3D curve at parameter value `u`.
+    ///
+    /// The tangent is along the first derivative of the curve, the normal
+    /// points towards the centre of curvature, and the binormal is
+    /// perpendicular to the osculating plane.
+    ///
+    /// Returns `None` where the frame is undefined: where the first
+    /// derivative of the curve is zero, or where the curvature is zero.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the parameter value at which to compute the frame
+    pub fn frenet_frame(&self, u: N) -> Option<Frame<N>> {
+        let ders = self.derivatives(u, 2);
+        let binormal = ders[1].cross(&ders[2]).try_normalize(N::zero())?;
+        let tangent = ders[1].try_normalize(N::zero())?;
+        Some(Frame {
+            origin: ders[0],
+            normal: binormal.cross(&tangent),
+            tangent,
+            binormal,
+        })
+    }
+
+    /// Computes rotation-minimizing frames along a 3D curve.
+    ///
+    /// Unlike Frenet frames, rotation-minimizing frames are defined along
+    /// straight sections and through inflections, and do not spin about the
+    /// tangent, which makes them suitable for sweeping profiles along a
+    /// curve. They are computed using the double reflection method (Wang,
+    /// Jüttler, Zheng and Liu, "Computation of rotation minimizing frames",
+    /// 2008), which propagates the frame from each parameter value to the
+    /// next. The accuracy of the frames improves as the parameter values
+    /// become more closely spaced.
+    ///
+    /// Returns `None` if the first derivative of the curve is zero at any of
+    /// the parameter values, or if `initial_normal` is parallel to the
+    /// tangent at the first parameter value.
+    ///
+    /// # Parameters
+    ///
+    /// * `us` - the parameter values at which to compute the frames, in
+    ///   increasing order
+    /// * `initial_normal` - the approximate normal of the first frame; the
+    ///   component along the tangent is removed
+    pub fn rotation_minimizing_frames(
+        &self,
+        us: &[N],
+        initial_normal: &Vector3<N>,
+    ) -> Option<Vec<Frame<N>>> {
+        let two = N::from_f64(2.0).unwrap();
+        let mut frames: Vec<Frame<N>> = Vec::with_capacity(us.len());
+        for &u in us {
+            let ders = self.derivatives(u, 1);
+            let origin = ders[0];
+            let tangent = ders[1].try_normalize(N::zero())?;
+
+            let normal = match frames.last() {
+                None => (initial_normal - tangent * tangent.dot(initial_normal))
+                    .try_normalize(N::zero())?,
+                Some(previous) => {
+                    // reflect the previous frame in the plane bisecting the
+                    // two points, and then in the plane bisecting the
+                    // reflected tangent and the new tangent
+                    let v1 = origin - previous.origin;
+                    let c1 = v1.norm_squared();
+                    let (normal_l, tangent_l) = if c1 == N::zero() {
+                        (previous.normal, previous.tangent)
+                    } else {
+                        (
+                            previous.normal - v1 * (two / c1 * v1.dot(&previous.normal)),
+                            previous.tangent - v1 * (two / c1 * v1.dot(&previous.tangent)),
+                        )
+                    };
+                    let v2 = tangent - tangent_l;
+                    let c2 = v2.norm_squared();
+                    let normal = if c2 == N::zero() {
+                        normal_l
+                    } else {
+                        normal_l - v2 * (two / c2 * v2.dot(&normal_l))
+                    };
+                    normal.normalize()
+                }
+            };
+
+            frames.push(Frame {
+                origin,
+                binormal: tangent.cross(&normal),
+                tangent,
+                normal,
+            });
+        }
+        Some(frames)
+    }
+}
+
+impl<N> Curve<N, Vector2<N>>
+where
+    N: 'static + ScalarT,
+{
+    /// Computes the signed curvature of a 2D curve at parameter value `u`.
+    ///
+    /// The signed curvature is positive where the curve turns
+    /// anti-clockwise (to the left), and negative where it turns clockwise.
+    /// Its magnitude is the curvature returned by `curvature`. Where the
+    /// first derivative of the curve is zero, the curvature is undefined and
+    /// zero is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the parameter value at which to compute the curvature
+    pub fn signed_curvature(&self, u: N) -> N {
+        let ders = self.derivatives(u, 2);
+        signed_curvature(&ders[1], &ders[2])
+    }
+
+    /// Finds the inflection points of a 2D curve.
+    ///
+    /// Returns the parameter values, in increasing order, where the signed
+    /// curvature changes sign. Within each Bézier segment, the sign of the
+    /// curvature is the sign of the polynomial `det[P, P', P'']`, where `P`
+    /// is the segment in homogeneous coordinates, so the inflections are
+    /// found as the roots of this polynomial. Knots where the curvature
+    /// changes sign discontinuously (for example, between the segments of a
+    /// quadratic curve) are also inflection points. Straight segments, where
+    /// the curvature is zero throughout, have no inflections; if the
+    /// curvature changes sign across a straight section, the end of the
+    /// straight section is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the accuracy required of the parameter values
+    pub fn inflections(&self, tolerance: N) -> Vec<N> {
+        if self.degree < 2 {
+            return Vec::new();
+        }
+        let mut inflections = Vec::new();
+        let mut previous_end = N::zero();
+        let breakpoints = self.breakpoints();
+        for (segment, span) in self
+            .bezier_decomposition()
+            .iter()
+            .zip(breakpoints.windows(2))
+        {
+            let (a, b) = (span[0], span[1]);
+            let x: Vec<N> = segment.iter().map(|h| h.point.x).collect();
+            let y: Vec<N> = segment.iter().map(|h| h.point.y).collect();
+            let w: Vec<N> = segment.iter().map(|h| h.weight).collect();
+            let det = homogeneous_determinant(&x, &y, &w);
+            if det.iter().all(|&d| d == N::zero()) {
+                continue;
+            }
+
+            // sign change across the knot at the start of the segment, using
+            // the signs just inside the segment where the ends of `det` are
+            // zero
+            let nonzero = |d: &&N| **d != N::zero();
+            let start = *det.iter().find(nonzero).unwrap();
+            if start * previous_end < N::zero() {
+                inflections.push(a);
+            }
+            previous_end = *det.iter().rev().find(nonzero).unwrap();
+
+            for t in bernstein_sign_changes(&det, tolerance / (b - a)) {
+                inflections.push(a + (b - a) * t);
+            }
+        }
+        inflections.dedup();
+        inflections
+    }
+
+    /// Finds the extrema of the signed curvature of a 2D curve.
+    ///
+    /// Returns the parameter values, in increasing order, where the signed
+    /// curvature reaches a local maximum or minimum within a knot span. Each
+    /// Bézier segment of the curve is sampled to find sign changes in the
+    /// derivative of the signed curvature, which is computed analytically
+    /// from the first three derivatives of the curve, and each sign change is
+    /// then refined by bisection.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the accuracy required of the parameter values
+    pub fn curvature_extrema(&self, tolerance: N) -> Vec<N> {
+        let n = SAMPLES_PER_DEGREE * self.degree;
+        let mut extrema: Vec<N> = Vec::new();
+        for segment in self.to_bezier_segments() {
+            let (a, b) = (segment.min_u(), segment.max_u());
+            let rate = |u: N| {
+                let ders = segment.derivatives(u, 3);
+                curvature_rate(&ders[1], &ders[2], &ders[3])
+            };
+
+            // samples where the rate is zero (to rounding error) are skipped,
+            // so that arcs of constant curvature have no extrema
+            let mut previous: Option<(N, N)> = None;
+            for i in 0..n + 1 {
+                let u1 = a + (b - a) * N::from_usize(i).unwrap() / N::from_usize(n).unwrap();
+                let g1 = rate(u1);
+                if g1 == N::zero() {
+                    continue;
+                }
+                if let Some((u0, g0)) = previous {
+                    if g0 * g1 < N::zero() {
+                        extrema.push(bisect(&rate, u0, u1, g0, tolerance));
+                    }
+                }
+                previous = Some((u1, g1));
+            }
+        }
+        extrema.dedup();
+        extrema
+    }
+}
+
+/// Computes the signed curvature of a 2D curve from its first and second
+/// derivatives.
+fn signed_curvature<N: 'static + ScalarT>(d1: &Vector2<N>, d2: &Vector2<N>) -> N {
+    let speed_squared = d1.norm_squared();
+    if speed_squared == N::zero() {
+        return N::zero();
+    }
+    d1.perp(d2) / (speed_squared * speed_squared.sqrt())
+}
+
+/// Computes a quantity with the same sign as the derivative of the signed
+/// curvature of a 2D curve, from its first three derivatives.
+///
+/// Writing `D = C' × C''` and `s = |C'|`, the signed curvature is `D / s³`,
+/// whose derivative has the sign of `D' s² - 3 D (C' · C'')`. Values which
+/// are small compared with the magnitudes of the two terms are rounding
+/// error, and are returned as zero.
+fn curvature_rate<N: 'static + ScalarT>(d1: &Vector2<N>, d2: &Vector2<N>, d3: &Vector2<N>) -> N {
+    let first = d1.perp(d3) * d1.norm_squared();
+    let second = N::from_f64(3.0).unwrap() * d1.perp(d2) * d1.dot(d2);
+    let scale = first.abs() + second.abs();
+    let rate = first - second;
+    if rate.abs() <= scale * N::from_f64(RATE_ROUNDING).unwrap() {
+        N::zero()
+    } else {
+        rate
+    }
+}
+
+/// Computes the Bernstein coefficients of `det[P, P', P'']` for a 2D Bézier
+/// segment in homogeneous coordinates `P = (x, y, w)`, up to a positive
+/// constant factor.
+fn homogeneous_determinant<N: ScalarT>(x: &[N], y: &[N], w: &[N]) -> Vec<N> {
+    let difference = |c: &[N]| -> Vec<N> { c.windows(2).map(|d| d[1] - d[0]).collect() };
+    let (x1, y1, w1) = (difference(x), difference(y), difference(w));
+    let (x2, y2, w2) = (difference(&x1), difference(&y1), difference(&w1));
+
+    // cofactor expansion along the first column
+    let minor = |a1: &[N], b2: &[N], b1: &[N], a2: &[N]| -> Vec<N> {
+        bernstein_product(a1, b2)
+            .into_iter()
+            .zip(bernstein_product(b1, a2))
+            .map(|(l, r)| l - r)
+            .collect()
+    };
+    let terms = [
+        bernstein_product(x, &minor(&y1, &w2, &w1, &y2)),
+        bernstein_product(y, &minor(&x1, &w2, &w1, &x2)),
+        bernstein_product(w, &minor(&x1, &y2, &y1, &x2)),
+    ];
+    (0..terms[0].len())
+        .map(|k| terms[0][k] - terms[1][k] + terms[2][k])
+        .collect()
+}
+
+/// Finds a root of a function between `u0` and `u1` by bisection, given
+/// that the function changes sign over the interval and has value `g0` at
+/// `u0`.
+fn bisect<N: ScalarT>(g: &impl Fn(N) -> N, mut u0: N, mut u1: N, mut g0: N, tolerance: N) -> N {
+    let half = N::from_f64(0.5).unwrap();
+    for _ in 0..MAX_BISECTIONS {
+        if u1 - u0 <= tolerance {
+            break;
+        }
+        let um = (u0 + u1) * half;
+        let gm = g(um);
+        if gm == N::zero() {
+            return um;
+        }
+        if gm * g0 < N::zero() {
+            u1 = um;
+        } else {
+            u0 = um;
+            g0 = gm;
+        }
+    }
+    (u0 + u1) * half
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{unit_circle, TC64};
+    use super::*;
+    use crate::knotvec::KnotVec;
+    use approx::assert_relative_eq;
+    use nalgebra::{Matrix3, Similarity2};
+
+    /// The twisted cubic `(t, t², t³)`, as a Bézier curve.
+    fn twisted_cubic() -> Curve<f64, Vector3<f64>> {
+        Curve::new(
+            3,
+            vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0 / 3.0, 0.0, 0.0),
+                Vector3::new(2.0 / 3.0, 1.0 / 3.0, 0.0),
+                Vector3::new(1.0, 1.0, 1.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// The unit circle from the examples, in the `z = 0` plane.
+    fn circle_3d() -> Curve<f64, Vector3<f64>> {
+        let circle = unit_circle();
+        Curve::new(
+            circle.degree(),
+            circle
+                .control_points()
+                .iter()
+                .map(|p| Vector3::new(p.x, p.y, 0.0))
+                .collect(),
+            circle.weights().clone(),
+            circle.knots().clone(),
+        )
+        .unwrap()
+    }
+
+    /// Checks that a frame is orthonormal and right-handed.
+    fn assert_orthonormal(frame: &Frame<f64>) {
+        assert_relative_eq!(frame.tangent().norm(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(frame.normal().norm(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(frame.tangent().dot(frame.normal()), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            frame.tangent().cross(frame.normal()),
+            *frame.binormal(),
+            epsilon = 1e-12
+        );
+    }
+
+    /// The curvature of a circle is the reciprocal of its radius.
+    #[test]
+    fn curvature_circle() {
+        let mut circle = unit_circle();
+        for &u in &[0.0, 0.1, 0.25, 0.6, 0.99, 1.0] {
+            assert_relative_eq!(circle.curvature(u), 1.0, epsilon = 1e-12);
+        }
+        circle
+            .transform(&Similarity2::from_scaling(2.0).to_homogeneous())
+            .unwrap();
+        for &u in &[0.0, 0.3, 0.7] {
+            assert_relative_eq!(circle.curvature(u), 0.5, epsilon = 1e-12);
+        }
+    }
+
+    /// Curvature and torsion of the twisted cubic.
+    #[test]
+    fn curvature_torsion_twisted_cubic() {
+        let cubic = twisted_cubic();
+        for &t in &[0.0f64, 0.2, 0.5, 0.8, 1.0] {
+            let d1 = Vector3::new(1.0, 2.0 * t, 3.0 * t * t);
+            let d2 = Vector3::new(0.0, 2.0, 6.0 * t);
+            let cross = d1.cross(&d2);
+            let kappa = cross.norm() / d1.norm().powi(3);
+            let tau = 12.0 / cross.norm_squared();
+            assert_relative_eq!(cubic.curvature(t), kappa, epsilon = 1e-12);
+            assert_relative_eq!(cubic.torsion(t), tau, epsilon = 1e-12);
+        }
+
+        // a planar curve has no torsion
+        let circle = circle_3d();
+        assert_relative_eq!(circle.torsion(0.3), 0.0, epsilon = 1e-12);
+    }
+
+    /// The Frenet normal of a circle points towards its centre.
+    #[test]
+    fn frenet_frame_circle() {
+        let circle = circle_3d();
+        for &u in &[0.0, 0.1, 0.4, 0.8] {
+            let frame = circle.frenet_frame(u).unwrap();
+            assert_orthonormal(&frame);
+            assert_relative_eq!(*frame.normal(), -frame.origin(), epsilon = 1e-12);
+            assert_relative_eq!(*frame.binormal(), Vector3::z(), epsilon = 1e-12);
+        }
+
+        // the frame is undefined on a straight line
+        let line = Curve::new(
+            1,
+            vec![Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0)],
+            vec![1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(line.frenet_frame(0.5), None);
+    }
+
+    /// Rotation-minimizing frames of a planar curve keep the plane normal,
+    /// and those of a twisted curve do not rotate about the tangent.
+    #[test]
+    fn rotation_minimizing_frames() {
+        let n = 200;
+        let us: Vec<f64> = (0..n + 1).map(|i| (i as f64) / (n as f64)).collect();
+
+        let frames = circle_3d()
+            .rotation_minimizing_frames(&us, &Vector3::new(0.0, 0.3, 1.0))
+            .unwrap();
+        for frame in &frames {
+            assert_orthonormal(frame);
+            assert_relative_eq!(*frame.normal(), Vector3::z(), epsilon = 1e-12);
+        }
+
+        let frames = twisted_cubic()
+            .rotation_minimizing_frames(&us, &Vector3::y())
+            .unwrap();
+        assert_relative_eq!(*frames[0].normal(), Vector3::y(), epsilon = 1e-12);
+        for pair in frames.windows(2) {
+            assert_orthonormal(&pair[1]);
+            let twist = (pair[1].normal() - pair[0].normal()).dot(pair[0].binormal());
+            assert!(twist.abs() < 1e-4);
+        }
+
+        assert_eq!(
+            twisted_cubic().rotation_minimizing_frames(&us, &Vector3::x()),
+            None
+        );
+    }
+
+    /// An S-shaped cubic with a single inflection.
+    fn s_curve() -> TC64 {
+        TC64::new(
+            3,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(2.0, -1.0),
+                Vector2::new(3.0, 0.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// The signed curvature of the circle is positive, since it turns
+    /// anti-clockwise.
+    #[test]
+    fn signed_curvature_circle() {
+        let circle = unit_circle();
+        assert_relative_eq!(circle.signed_curvature(0.3), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            circle.reversed().signed_curvature(0.3),
+            -1.0,
+            epsilon = 1e-12
+        );
+    }
+
+    /// Inflections of an S-shaped curve, a circle and a quadratic spline.
+    #[test]
+    fn inflections() {
+        // the S curve is symmetric about its middle
+        let inflections = s_curve().inflections(1e-12);
+        assert_eq!(inflections.len(), 1);
+        assert_relative_eq!(inflections[0], 0.5, epsilon = 1e-12);
+
+        // also after a projective transformation, which changes the weights
+        let mut projected = s_curve();
+        let matrix = Matrix3::new(1.0, 0.2, 0.0, 0.1, 1.0, 0.0, 0.1, 0.05, 1.0);
+        projected.transform(&matrix).unwrap();
+        let u = projected.inflections(1e-12)[0];
+        assert_relative_eq!(projected.signed_curvature(u - 1e-4).signum(), -1.0);
+        assert_relative_eq!(projected.signed_curvature(u + 1e-4).signum(), 1.0);
+
+        assert!(unit_circle().inflections(1e-12).is_empty());
+
+        // the curvature of a quadratic spline changes sign at a knot
+        let wave = TC64::new(
+            2,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(2.0, -1.0),
+                Vector2::new(3.0, 0.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 2.0, 2.0, 2.0]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(wave.inflections(1e-12), vec![1.0]);
+
+        // a curve whose first derivative vanishes at its start has zero
+        // curvature there, but does not change direction of turning
+        let cusp = TC64::new(
+            3,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(2.0, 0.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        assert!(cusp.inflections(1e-12).is_empty());
+        assert!(cusp.reversed().inflections(1e-12).is_empty());
+    }
+
+    /// Extrema of curvature of the S curve, checked against sampled
+    /// curvature.
+    #[test]
+    fn curvature_extrema() {
+        let curve = s_curve();
+        let extrema = curve.curvature_extrema(1e-12);
+        assert_eq!(extrema.len(), 2);
+        assert_relative_eq!(extrema[0], 1.0 - extrema[1], epsilon = 1e-9);
+        for &u in &extrema {
+            let k = curve.signed_curvature(u);
+            for &h in &[1e-3, -1e-3] {
+                assert!(curve.signed_curvature(u + h).abs() < k.abs());
+            }
+        }
+
+        // the curvature of a circle is constant
+        assert!(unit_circle().curvature_extrema(1e-12).is_empty());
+    }
+}
@@ -0,0 +1,440 @@
+use super::arclength::GAUSS_LEGENDRE_5;
+use super::banded::BandedMatrix;
+use super::interpolate::matrix_to_points;
+use super::{Curve, CurveError, Result};
+use crate::algebra::{ScalarT, VectorT};
+use nalgebra::DMatrix;
+
+/// Number of bisection steps when searching for the strength of fairing
+/// which just meets the tolerance.
+const FAIRING_ITERATIONS: usize = 60;
+
+/// Range of the strength of fairing, relative to the scale of the energy,
+/// over which the bisection searches.
+const FAIRING_RANGE: f64 = 1e6;
+
+/// Measure of the wobbliness of a curve, which fairing minimizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FairingEnergy {
+    /// The integral of the squared second derivative, which approximates the
+    /// bending energy of a thin elastic beam. Minimizing it straightens the
+    /// curve, and removes bumps.
+    SecondDerivative,
+
+    /// The integral of the squared third derivative. Minimizing it evens out
+    /// the rate of change of curvature, which removes wobbles while keeping
+    /// arcs round. The curve must have a degree of at least three.
+    ThirdDerivative,
+}
+
+impl FairingEnergy {
+    /// Returns the order of the derivative whose square is integrated.
+    fn order(self) -> usize {
+        match self {
+            FairingEnergy::SecondDerivative => 2,
+            FairingEnergy::ThirdDerivative => 3,
+        }
+    }
+}
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Fairs the curve, by moving its control points to reduce its energy
+    /// while keeping each of them within a tolerance of where it started.
+    ///
+    /// The energy is the integral over the parameter range of the curve of
+    /// the squared derivative chosen by `energy`. The control points minimize
+    /// the energy plus a penalty on the squared distance of each control
+    /// point from its original position. The strength of the penalty is
+    /// found by bisection, so that the control point which moves furthest
+    /// moves by no more than `tolerance`, which gives the fairest curve that
+    /// the penalty allows.
+    ///
+    /// Unclamped and periodic curves are clamped first (see `clamp`), so the
+    /// faired curve is always clamped. The knots and weights of the clamped
+    /// curve are unchanged, and so are its first and last control points, so
+    /// that the ends of the curve stay in place, and a closed curve stays
+    /// closed. For rational curves, the energy is that of the curve in
+    /// homogeneous coordinates, which is the same as that of the curve
+    /// itself when all the weights are equal.
+    ///
+    /// Returns the faired curve, along with the largest distance that any
+    /// control point of the clamped curve moved. An error is returned if the
+    /// tolerance is negative, if the degree of the curve is less than the
+    /// order of the derivative in the energy, or if the tolerance is too
+    /// small for even the strongest penalty that the search tries.
+    ///
+    /// # Parameters
+    ///
+    /// * `energy` - the energy to minimize
+    /// * `tolerance` - the maximum distance that any control point may move
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::{Curve, FairingEnergy, Parameterization};
+    /// use nalgebra::Vector2;
+    ///
+    /// // interpolating noisy points gives a wobbly curve
+    /// let points: Vec<Vector2<f64>> = (0..20)
+    ///     .map(|i| {
+    ///         let x = (i as f64) / 4.0;
+    ///         let noise = if i % 2 == 0 { 0.01 } else { -0.01 };
+    ///         Vector2::new(x, x.sin() + noise)
+    ///     })
+    ///     .collect();
+    /// let curve = Curve::interpolate(&points, 3, Parameterization::ChordLength).unwrap();
+    /// let (faired, moved) = curve.fair(FairingEnergy::ThirdDerivative, 0.05).unwrap();
+    /// assert!(moved <= 0.05);
+    /// let energy = FairingEnergy::ThirdDerivative;
+    /// assert!(faired.energy(energy).unwrap() < curve.energy(energy).unwrap() / 10.0);
+    /// ```
+    pub fn fair(&self, energy: FairingEnergy, tolerance: N) -> Result<(Self, N)> {
+        if tolerance < N::zero() {
+            return Err(CurveError::NegativeTolerance);
+        }
+        let curve = self.clamp();
+        let gram = curve.energy_matrix(energy)?;
+        let n = curve.control_points.len();
+        if n <= 2 || tolerance == N::zero() {
+            return Ok((curve, N::zero()));
+        }
+
+        // the energy of the free control points, and its gradient at their
+        // original positions, which includes their coupling to the fixed end
+        // control points
+        let free = n - 2;
+        let p = curve.degree;
+        let dimension = curve.control_points[0].dimension();
+        let mut lhs = BandedMatrix::zeros(free, p, p);
+        let mut gradient = DMatrix::zeros(free, dimension);
+        for i in 0..free {
+            for j in gram.band(i + 1) {
+                if j >= 1 && j <= free {
+                    lhs[(i, j - 1)] = gram[(i + 1, j)];
+                }
+                for d in 0..dimension {
+                    gradient[(i, d)] += gram[(i + 1, j)] * curve.control_points[j][d];
+                }
+            }
+        }
+
+        // the control points are moved rather than solved for directly, so
+        // that a curve which is already fair is not disturbed by rounding
+        // errors. The penalty is scaled by the size of the energy, so that
+        // the range of the search does not depend on the size of the curve.
+        let scale =
+            (0..free).fold(N::zero(), |a, i| a + lhs[(i, i)]) / N::from_usize(free).unwrap();
+        if scale <= N::zero() {
+            return Ok((curve, N::zero()));
+        }
+        let rhs = -gradient;
+        let solve = |log_strength: N| -> Result<(Vec<V>, N)> {
+            let mut matrix = lhs.clone();
+            for i in 0..free {
+                matrix[(i, i)] += scale * log_strength.exp();
+            }
+            let solution = matrix.solve(&rhs).ok_or(CurveError::SingularSystem)?;
+            let offsets: Vec<V> = matrix_to_points(&solution);
+            let moved = offsets.iter().fold(N::zero(), |a, o| a.max(o.norm()));
+            let points = offsets
+                .into_iter()
+                .zip(&curve.control_points[1..n - 1])
+                .map(|(o, p)| p.clone() + o)
+                .collect();
+            Ok((points, moved))
+        };
+
+        // a weaker penalty gives a fairer curve, which moves further
+        let range = N::from_f64(FAIRING_RANGE).unwrap().ln();
+        let (mut weak, mut strong) = (-range, range);
+        let mut best = solve(weak)?;
+        if best.1 > tolerance {
+            best = solve(strong)?;
+            if best.1 > tolerance {
+                return Err(CurveError::FairingToleranceTooSmall);
+            }
+            for _ in 0..FAIRING_ITERATIONS {
+                let mid = (weak + strong) / N::from_f64(2.0).unwrap();
+                let result = solve(mid)?;
+                if result.1 <= tolerance {
+                    strong = mid;
+                    best = result;
+                } else {
+                    weak = mid;
+                }
+            }
+        }
+
+        let (interior, moved) = best;
+        let mut control_points = Vec::with_capacity(n);
+        control_points.push(curve.control_points[0].clone());
+        control_points.extend(interior);
+        control_points.push(curve.control_points[n - 1].clone());
+        let faired = Curve::new(
+            curve.degree,
+            control_points,
+            curve.weights.clone(),
+            curve.knots.clone(),
+        )?;
+        Ok((faired, moved))
+    }
+
+    /// Computes the energy of the curve, as minimized by `fair`.
+    ///
+    /// An error is returned if the degree of the curve is less than the order
+    /// of the derivative in the energy.
+    ///
+    /// # Parameters
+    ///
+    /// * `energy` - the energy to compute
+    pub fn energy(&self, energy: FairingEnergy) -> Result<N> {
+        let gram = self.energy_matrix(energy)?;
+        let n = self.control_points.len();
+        let dimension = self.control_points[0].dimension();
+        let mut total = N::zero();
+        for i in 0..n {
+            for j in gram.band(i) {
+                for d in 0..dimension {
+                    total += gram[(i, j)] * self.control_points[i][d] * self.control_points[j][d];
+                }
+            }
+        }
+        Ok(total)
+    }
+
+    /// Computes the matrix of the energy as a quadratic form in the control
+    /// points.
+    ///
+    /// Element `(i, j)` is the integral of the product of the derivatives of
+    /// basis functions `i` and `j`, each multiplied by its weight. Since the
+    /// basis functions overlap only when `|i - j| <= degree`, the matrix is
+    /// banded. Each knot span is integrated by 5-point Gauss-Legendre
+    /// quadrature, which is exact when the degree exceeds the order of the
+    /// derivative by no more than four. For higher degrees, the span is split
+    /// into pieces to keep the integral accurate.
+    fn energy_matrix(&self, energy: FairingEnergy) -> Result<BandedMatrix<N>> {
+        let order = energy.order();
+        let p = self.degree;
+        if p < order {
+            return Err(CurveError::FairingDegreeTooLow { degree: p, order });
+        }
+
+        let n = self.control_points.len();
+        let pieces = 1 + (p - order) / 5;
+        let mut gram = BandedMatrix::zeros(n, p, p);
+        for span in self.breakpoints().windows(2) {
+            let width = (span[1] - span[0]) / N::from_usize(pieces).unwrap();
+            let half_width = width / N::from_f64(2.0).unwrap();
+            for piece in 0..pieces {
+                let mid = span[0] + width * N::from_usize(piece).unwrap() + half_width;
+                for &(x, w) in GAUSS_LEGENDRE_5.iter() {
+                    let u = mid + half_width * N::from_f64(x).unwrap();
+                    let k = self.find_span(u);
+                    let ders = self.knots.basis_function_derivatives(p, k, u, order);
+                    let values: Vec<N> = (0..=p)
+                        .map(|j| ders[order][j] * self.weights[k - p + j])
+                        .collect();
+                    let w = N::from_f64(w).unwrap() * half_width;
+                    for a in 0..=p {
+                        for b in 0..=p {
+                            gram[(k - p + a, k - p + b)] += w * values[a] * values[b];
+                        }
+                    }
+                }
+            }
+        }
+        Ok(gram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::periodic_cubic;
+    use super::*;
+    use crate::curve::{CurveKind, Parameterization};
+    use crate::knotvec::KnotVec;
+    use approx::assert_relative_eq;
+    use nalgebra::Vector2;
+
+    /// An interpolating curve through points on a sine wave, with
+    /// alternating noise.
+    fn wobbly_curve() -> Curve<f64, Vector2<f64>> {
+        let points: Vec<Vector2<f64>> = (0..20)
+            .map(|i| {
+                let x = (i as f64) / 4.0;
+                let noise = if i % 2 == 0 { 0.02 } else { -0.02 };
+                Vector2::new(x, x.sin() + noise)
+            })
+            .collect();
+        Curve::interpolate(&points, 3, Parameterization::ChordLength).unwrap()
+    }
+
+    /// The energy of curves with known derivatives.
+    #[test]
+    fn energy() {
+        // a parabola y = x^2 over 0..=1 has a second derivative of 2
+        let parabola = Curve::new(
+            2,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(0.5, 0.0),
+                Vector2::new(1.0, 1.0),
+            ],
+            vec![1.0; 3],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        assert_relative_eq!(
+            parabola.energy(FairingEnergy::SecondDerivative).unwrap(),
+            4.0,
+            epsilon = 1e-12
+        );
+
+        // inserting knots does not change the energy
+        let refined = parabola.refine_knots(&[0.3, 0.6]).unwrap();
+        assert_relative_eq!(
+            refined.energy(FairingEnergy::SecondDerivative).unwrap(),
+            4.0,
+            epsilon = 1e-12
+        );
+
+        // the energy of a cubic y = x^3, with third derivative 6, stays
+        // accurate after elevating its degree beyond the quadrature rule
+        let cubic = Curve::new(
+            3,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0 / 3.0, 0.0),
+                Vector2::new(2.0 / 3.0, 0.0),
+                Vector2::new(1.0, 1.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        let elevated = cubic.elevate_degree(9);
+        for curve in &[cubic, elevated] {
+            assert_relative_eq!(
+                curve.energy(FairingEnergy::ThirdDerivative).unwrap(),
+                36.0,
+                epsilon = 1e-9
+            );
+        }
+    }
+
+    /// Fairing reduces the energy of a curve, more so as the tolerance
+    /// grows, without moving any control point further than the tolerance.
+    #[test]
+    fn fair() {
+        let curve = wobbly_curve();
+        for &energy in &[
+            FairingEnergy::SecondDerivative,
+            FairingEnergy::ThirdDerivative,
+        ] {
+            let mut previous = curve.energy(energy).unwrap();
+            for &tolerance in &[0.01, 0.05, 0.2] {
+                let (faired, moved) = curve.fair(energy, tolerance).unwrap();
+                assert!(moved <= tolerance);
+                assert_relative_eq!(moved, tolerance, max_relative = 1e-6);
+                assert_eq!(faired.knots(), curve.knots());
+                assert_eq!(faired.weights(), curve.weights());
+                let n = curve.control_points().len();
+                assert_eq!(faired.control_points()[0], curve.control_points()[0]);
+                assert_eq!(
+                    faired.control_points()[n - 1],
+                    curve.control_points()[n - 1]
+                );
+                for (a, b) in faired.control_points().iter().zip(curve.control_points()) {
+                    assert!((a - b).norm() <= tolerance + 1e-12);
+                }
+                let faired_energy = faired.energy(energy).unwrap();
+                assert!(faired_energy < previous);
+                previous = faired_energy;
+            }
+        }
+    }
+
+    /// Fair curves are unchanged by fairing, and a generous tolerance
+    /// straightens a curve almost completely.
+    #[test]
+    fn fair_limits() {
+        let line = Curve::new(
+            3,
+            // control points at the Greville abscissae give a linear
+            // parameterization
+            [0.0, 1.0 / 3.0, 1.0, 2.0, 8.0 / 3.0, 3.0]
+                .iter()
+                .map(|&x| Vector2::new(x, 2.0 * x))
+                .collect(),
+            vec![1.0; 6],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 3.0, 3.0]).unwrap(),
+        )
+        .unwrap();
+        let (faired, moved) = line.fair(FairingEnergy::ThirdDerivative, 1.0).unwrap();
+        assert_relative_eq!(moved, 0.0, epsilon = 1e-9);
+        for (a, b) in faired.control_points().iter().zip(line.control_points()) {
+            assert_relative_eq!(a, b, epsilon = 1e-9);
+        }
+
+        let curve = wobbly_curve();
+        let (straight, _) = curve.fair(FairingEnergy::SecondDerivative, 100.0).unwrap();
+        let energy = curve.energy(FairingEnergy::SecondDerivative).unwrap();
+        assert!(straight.energy(FairingEnergy::SecondDerivative).unwrap() < energy * 1e-5);
+
+        let (unchanged, moved) = curve.fair(FairingEnergy::SecondDerivative, 0.0).unwrap();
+        assert_eq!(unchanged, curve);
+        assert_eq!(moved, 0.0);
+    }
+
+    /// The degree of a curve must be at least the order of the derivative in
+    /// the energy.
+    #[test]
+    fn fair_errors() {
+        let quadratic = Curve::new(
+            2,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(2.0, 0.0),
+            ],
+            vec![1.0; 3],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            quadratic.fair(FairingEnergy::ThirdDerivative, 0.1),
+            Err(CurveError::FairingDegreeTooLow {
+                degree: 2,
+                order: 3
+            })
+        );
+        assert!(quadratic.fair(FairingEnergy::SecondDerivative, 0.1).is_ok());
+
+        // a tolerance which even the strongest penalty cannot meet
+        assert_eq!(
+            wobbly_curve().fair(FairingEnergy::ThirdDerivative, 1e-15),
+            Err(CurveError::FairingToleranceTooSmall)
+        );
+        assert_eq!(
+            wobbly_curve().fair(FairingEnergy::ThirdDerivative, -0.1),
+            Err(CurveError::NegativeTolerance)
+        );
+    }
+
+    /// Periodic curves are clamped before fairing, so that they stay closed.
+    #[test]
+    fn fair_periodic() {
+        let curve = periodic_cubic();
+        let energy = FairingEnergy::SecondDerivative;
+        let (faired, moved) = curve.fair(energy, 0.2).unwrap();
+        assert_eq!(faired.kind(), CurveKind::Clamped);
+        assert!(moved <= 0.2);
+        assert_eq!(faired.de_boor(0.0), curve.de_boor(0.0));
+        assert_relative_eq!(faired.de_boor(0.0), faired.de_boor(5.0), epsilon = 1e-12);
+        assert!(faired.energy(energy).unwrap() < curve.energy(energy).unwrap());
+    }
+}
@@ -0,0 +1,511 @@
+use super::banded::BandedMatrix;
+use super::{Curve, CurveError, Result};
+use crate::algebra::{ScalarT, VectorT};
+use crate::knotvec::KnotVec;
+use nalgebra::DMatrix;
+
+/// Method of choosing the parameter values at which a curve passes through
+/// data points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parameterization {
+    /// Parameter values are evenly spaced, regardless of the spacing of the
+    /// points. This can produce unwanted wiggles when the points are
+    /// unevenly spaced.
+    Uniform,
+
+    /// The spacing of the parameter values is proportional to the distance
+    /// between successive points.
+    ChordLength,
+
+    /// The spacing of the parameter values is proportional to the square
+    /// root of the distance between successive points. This is often the
+    /// best choice when the data turns sharply (Lee, "Choosing nodes in
+    /// parametric curve interpolation", 1989).
+    Centripetal,
+}
+
+/// A derivative which an interpolating curve must match at one of the
+/// points it passes through.
+///
+/// Derivatives are taken with respect to the parameter of the curve, which
+/// runs from `0` to `1`, so their magnitude affects the shape of the curve. A
+/// tangent whose length is roughly the total length of the curve usually
+/// gives good results.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DerivativeConstraint<V> {
+    /// The index of the point at which the derivative applies.
+    pub index: usize,
+
+    /// The order of the derivative (`1` for a tangent, `2` for a second
+    /// derivative), which must be less than the degree of the curve.
+    pub order: usize,
+
+    /// The value of the derivative.
+    pub derivative: V,
+}
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Creates a curve which passes through a sequence of points.
+    ///
+    /// The curve is a non-rational B-spline of the given degree, with one
+    /// control point for every data point, and a parameter range of `0..=1`.
+    /// A parameter value is chosen for each point according to
+    /// `parameterization`, and the knots are placed by averaging these
+    /// parameter values, which ensures that the linear system for the control
+    /// points is well-conditioned (equation 9.8 of The NURBS Book). The
+    /// control points are then found by solving the linear system which
+    /// requires the curve to pass through each point at its parameter value
+    /// (algorithm A9.1 from The NURBS Book).
+    ///
+    /// An error is returned if there are not more points than the degree, or
+    /// if successive points coincide (for the chord length and centripetal
+    /// parameterizations).
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the points which the curve must pass through, in order
+    /// * `degree` - the degree of the curve
+    /// * `parameterization` - the method used to choose the parameter value of
+    ///   each point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::{Curve, Parameterization};
+    /// use nalgebra::Vector2;
+    ///
+    /// let points = vec![
+    ///     Vector2::new(0.0, 0.0),
+    ///     Vector2::new(1.0, 2.0),
+    ///     Vector2::new(3.0, 2.0),
+    ///     Vector2::new(4.0, 0.0),
+    ///     Vector2::new(6.0, 1.0),
+    /// ];
+    /// let curve = Curve::interpolate(&points, 3, Parameterization::ChordLength).unwrap();
+    /// for point in &points {
+    ///     let (_, _, distance) = curve.closest_point(point, 1e-12);
+    ///     assert!(distance < 1e-9);
+    /// }
+    /// ```
+    pub fn interpolate(
+        points: &[V],
+        degree: usize,
+        parameterization: Parameterization,
+    ) -> Result<Self> {
+        Curve::interpolate_with_derivatives(points, &[], degree, parameterization)
+    }
+
+    /// Creates a curve which passes through a sequence of points, matching
+    /// given derivatives at some of the points.
+    ///
+    /// This is like `interpolate`, except that the curve also matches each
+    /// of the derivative constraints. Constraining the first derivatives at
+    /// the ends of the curve allows it to join existing geometry with
+    /// matching tangents, while constraining derivatives at interior points
+    /// gives Hermite-style interpolation. Each constraint adds a control
+    /// point to the curve. The parameter values of the points are chosen as
+    /// for `interpolate`. The knots are placed by averaging these parameter
+    /// values, with the parameter value of each point repeated once for each
+    /// of its constraints, which generalizes the knot placement of section
+    /// 9.2.2 of The NURBS Book.
+    ///
+    /// An error is returned if there are fewer than two points, if the
+    /// number of points plus the number of constraints does not exceed the
+    /// degree, or if any constraint refers to a point which does not exist,
+    /// has an order of zero or an order which is not less than the degree, or
+    /// is repeated. Limiting the order in this way ensures that the averaged
+    /// knots never have a multiplicity greater than one inside the curve, or
+    /// repeat the knots at its ends.
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the points which the curve must pass through, in order
+    /// * `derivatives` - the derivatives which the curve must match
+    /// * `degree` - the degree of the curve
+    /// * `parameterization` - the method used to choose the parameter value of
+    ///   each point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::{Curve, DerivativeConstraint, Parameterization};
+    /// use nalgebra::Vector2;
+    ///
+    /// // a cubic Hermite curve, with tangents at either end
+    /// let points = [Vector2::new(0.0, 0.0), Vector2::new(3.0, 0.0)];
+    /// let derivatives = [
+    ///     DerivativeConstraint { index: 0, order: 1, derivative: Vector2::new(0.0, 3.0) },
+    ///     DerivativeConstraint { index: 1, order: 1, derivative: Vector2::new(0.0, -3.0) },
+    /// ];
+    /// let curve = Curve::interpolate_with_derivatives(
+    ///     &points,
+    ///     &derivatives,
+    ///     3,
+    ///     Parameterization::ChordLength,
+    /// )
+    /// .unwrap();
+    /// assert!((curve.control_points()[1] - Vector2::new(0.0, 1.0)).norm() < 1e-12);
+    /// assert!((curve.control_points()[2] - Vector2::new(3.0, 1.0)).norm() < 1e-12);
+    /// ```
+    pub fn interpolate_with_derivatives(
+        points: &[V],
+        derivatives: &[DerivativeConstraint<V>],
+        degree: usize,
+        parameterization: Parameterization,
+    ) -> Result<Self> {
+        let n_conditions = points.len() + derivatives.len();
+        if degree == 0 {
+            return Err(CurveError::InvalidDegree);
+        } else if points.len() < 2 || n_conditions <= degree {
+            return Err(CurveError::InsufficientControlPoints {
+                degree,
+                number_supplied: n_conditions,
+            });
+        }
+        for (i, constraint) in derivatives.iter().enumerate() {
+            let repeated = derivatives[..i]
+                .iter()
+                .any(|c| c.index == constraint.index && c.order == constraint.order);
+            if constraint.index >= points.len()
+                || constraint.order == 0
+                || constraint.order >= degree
+                || repeated
+            {
+                return Err(CurveError::InvalidDerivativeConstraint);
+            }
+        }
+
+        // each point is repeated in the parameter values used to place the
+        // knots once for each of its constraints
+        let params = parameterize(points, parameterization)?;
+        let mut knot_params = Vec::with_capacity(n_conditions);
+        for (k, &u) in params.iter().enumerate() {
+            let count = derivatives.iter().filter(|c| c.index == k).count();
+            knot_params.extend(std::iter::repeat_n(u, count + 1));
+        }
+        let knots = averaged_knots(&knot_params, degree);
+
+        // one row of the linear system for each point, followed by a row
+        // for each of its constraints, which keeps the system banded
+        let mut rows = Vec::with_capacity(n_conditions);
+        let mut rhs = Vec::with_capacity(n_conditions);
+        for ((k, point), row) in points
+            .iter()
+            .enumerate()
+            .zip(basis_rows(&knots, degree, &params))
+        {
+            rows.push(row);
+            rhs.push(point.clone());
+            let u = params[k];
+            let span = knots.find_span(u);
+            for constraint in derivatives.iter().filter(|c| c.index == k) {
+                let ders = knots.basis_function_derivatives(degree, span, u, constraint.order);
+                rows.push((span - degree, ders[constraint.order].clone()));
+                rhs.push(constraint.derivative.clone());
+            }
+        }
+
+        let control_points = solve_for_points(BandedMatrix::from_rows(&rows), &rhs)?;
+        let weights = vec![N::one(); control_points.len()];
+        Curve::new(degree, control_points, weights, knots)
+    }
+}
+
+/// Chooses parameter values in the range `0..=1` for a sequence of points.
+pub(super) fn parameterize<N, V>(points: &[V], parameterization: Parameterization) -> Result<Vec<N>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let spacing: Vec<N> = points
+        .windows(2)
+        .map(|w| {
+            let chord = (w[1].clone() - w[0].clone()).norm();
+            match parameterization {
+                Parameterization::Uniform => N::one(),
+                Parameterization::ChordLength => chord,
+                Parameterization::Centripetal => chord.sqrt(),
+            }
+        })
+        .collect();
+    if spacing.iter().any(|&d| d <= N::zero()) {
+        return Err(CurveError::CoincidentPoints);
+    }
+
+    let total = spacing.iter().fold(N::zero(), |a, &b| a + b);
+    let mut params = Vec::with_capacity(points.len());
+    let mut sum = N::zero();
+    params.push(N::zero());
+    for &d in &spacing[..spacing.len() - 1] {
+        sum += d;
+        params.push(sum / total);
+    }
+    params.push(N::one());
+    Ok(params)
+}
+
+/// Places the knots of a curve by averaging parameter values, so that every
+/// knot span contains at least one parameter value.
+pub(super) fn averaged_knots<N: ScalarT>(params: &[N], degree: usize) -> KnotVec<N> {
+    let n = params.len() - 1;
+    let mut knots = vec![N::zero(); degree + 1];
+    for j in 1..n + 1 - degree {
+        let sum = params[j..j + degree].iter().fold(N::zero(), |a, &b| a + b);
+        knots.push(sum / N::from_usize(degree).unwrap());
+    }
+    knots.extend(std::iter::repeat_n(N::one(), degree + 1));
+    KnotVec::new(knots).expect("averaged knots must be valid")
+}
+
+/// Evaluates the non-zero B-spline basis functions at each parameter value.
+///
+/// Returns a row for each parameter value, as the index of the first
+/// non-zero basis function and the values of the `degree + 1` basis
+/// functions from there onwards.
+pub(super) fn basis_rows<N: ScalarT>(
+    knots: &KnotVec<N>,
+    degree: usize,
+    params: &[N],
+) -> Vec<(usize, Vec<N>)> {
+    params
+        .iter()
+        .map(|&u| {
+            let span = knots.find_span(u);
+            let mut basis = knots.basis_function_derivatives(degree, span, u, 0);
+            (span - degree, basis.swap_remove(0))
+        })
+        .collect()
+}
+
+/// Solves the linear system `matrix * control_points = points` for the
+/// control points, treating each component of the points separately.
+pub(super) fn solve_for_points<N, V>(matrix: BandedMatrix<N>, points: &[V]) -> Result<Vec<V>>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let dimension = points[0].dimension();
+    let rhs = DMatrix::from_fn(points.len(), dimension, |k, d| points[k][d]);
+    let solution = matrix.solve(&rhs).ok_or(CurveError::SingularSystem)?;
+    Ok(matrix_to_points(&solution))
+}
+
+/// Converts the rows of a matrix into points.
+pub(super) fn matrix_to_points<N, V>(matrix: &DMatrix<N>) -> Vec<V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    (0..matrix.nrows())
+        .map(|i| {
+            let mut point = V::zero();
+            for d in 0..matrix.ncols() {
+                point[d] = matrix[(i, d)];
+            }
+            point
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::helix_points;
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{Vector2, Vector3};
+
+    /// Interpolating curves pass through every point, at the chosen
+    /// parameter values.
+    #[test]
+    fn interpolate() {
+        let points = helix_points();
+        for &parameterization in &[
+            Parameterization::Uniform,
+            Parameterization::ChordLength,
+            Parameterization::Centripetal,
+        ] {
+            for degree in 1..5 {
+                let curve = Curve::interpolate(&points, degree, parameterization).unwrap();
+                assert_eq!(curve.degree(), degree);
+                assert_eq!(curve.control_points().len(), points.len());
+                let params = parameterize(&points, parameterization).unwrap();
+                for (point, &u) in points.iter().zip(&params) {
+                    assert_relative_eq!(curve.de_boor(u), point, epsilon = 1e-9);
+                }
+            }
+        }
+    }
+
+    /// Interpolating curves match derivative constraints at the ends and at
+    /// interior points.
+    #[test]
+    fn interpolate_with_derivatives() {
+        let points = helix_points();
+        let tangent = |t: f64| Vector3::new(-t.sin(), t.cos(), 0.2) * 8.0;
+        let constraint = |index, order, derivative| DerivativeConstraint {
+            index,
+            order,
+            derivative,
+        };
+        let cases = [
+            vec![
+                constraint(0, 1, tangent(0.0)),
+                constraint(8, 1, tangent(5.5)),
+            ],
+            vec![
+                constraint(0, 1, tangent(0.0)),
+                constraint(0, 2, Vector3::new(-1.0, 0.0, 0.0) * 64.0),
+                constraint(8, 2, Vector3::zeros()),
+            ],
+            vec![
+                constraint(3, 1, tangent(1.2)),
+                constraint(5, 1, tangent(2.1)),
+                constraint(5, 2, Vector3::new(1.0, -2.0, 0.0)),
+            ],
+        ];
+        for derivatives in &cases {
+            for degree in 3..6 {
+                let curve = Curve::interpolate_with_derivatives(
+                    &points,
+                    derivatives,
+                    degree,
+                    Parameterization::ChordLength,
+                )
+                .unwrap();
+                assert_eq!(
+                    curve.control_points().len(),
+                    points.len() + derivatives.len()
+                );
+                let params = parameterize(&points, Parameterization::ChordLength).unwrap();
+                for (point, &u) in points.iter().zip(&params) {
+                    assert_relative_eq!(curve.de_boor(u), point, epsilon = 1e-9);
+                }
+                for c in derivatives {
+                    let ders = curve.derivatives(params[c.index], c.order);
+                    assert_relative_eq!(ders[c.order], c.derivative, epsilon = 1e-8);
+                }
+            }
+        }
+
+        // a cubic Hermite segment is a Bézier curve
+        let ends = [Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)];
+        let (d0, d1) = (Vector2::new(3.0, 0.0), Vector2::new(0.0, 6.0));
+        let hermite = Curve::interpolate_with_derivatives(
+            &ends,
+            &[
+                DerivativeConstraint {
+                    index: 0,
+                    order: 1,
+                    derivative: d0,
+                },
+                DerivativeConstraint {
+                    index: 1,
+                    order: 1,
+                    derivative: d1,
+                },
+            ],
+            3,
+            Parameterization::Uniform,
+        )
+        .unwrap();
+        let expected = [ends[0], ends[0] + d0 / 3.0, ends[1] - d1 / 3.0, ends[1]];
+        for (cp, expected) in hermite.control_points().iter().zip(expected.iter()) {
+            assert_relative_eq!(cp, expected, epsilon = 1e-12);
+        }
+    }
+
+    /// Invalid derivative constraints.
+    #[test]
+    fn interpolate_with_derivatives_errors() {
+        let points = helix_points();
+        let check = |index, order| {
+            let derivatives = [
+                DerivativeConstraint {
+                    index: 0,
+                    order: 1,
+                    derivative: Vector3::x(),
+                },
+                DerivativeConstraint {
+                    index,
+                    order,
+                    derivative: Vector3::y(),
+                },
+            ];
+            Curve::interpolate_with_derivatives(
+                &points,
+                &derivatives,
+                3,
+                Parameterization::ChordLength,
+            )
+        };
+        assert!(check(1, 2).is_ok());
+        assert_eq!(check(9, 1), Err(CurveError::InvalidDerivativeConstraint));
+        assert_eq!(check(1, 0), Err(CurveError::InvalidDerivativeConstraint));
+        assert_eq!(check(1, 3), Err(CurveError::InvalidDerivativeConstraint));
+        assert_eq!(check(0, 1), Err(CurveError::InvalidDerivativeConstraint));
+        assert_eq!(
+            Curve::interpolate_with_derivatives(
+                &points[..1],
+                &[],
+                1,
+                Parameterization::ChordLength
+            ),
+            Err(CurveError::InsufficientControlPoints {
+                degree: 1,
+                number_supplied: 1
+            })
+        );
+    }
+
+    /// Choosing parameter values for points.
+    #[test]
+    fn parameterize_points() {
+        let points = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 4.0),
+            Vector2::new(1.0, 8.0),
+        ];
+        let uniform = parameterize(&points, Parameterization::Uniform).unwrap();
+        assert_relative_eq!(
+            uniform.as_slice(),
+            [0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0].as_ref()
+        );
+        let chord = parameterize(&points, Parameterization::ChordLength).unwrap();
+        assert_relative_eq!(chord.as_slice(), [0.0, 1.0 / 9.0, 5.0 / 9.0, 1.0].as_ref());
+        let centripetal = parameterize(&points, Parameterization::Centripetal).unwrap();
+        assert_relative_eq!(centripetal.as_slice(), [0.0, 0.2, 0.6, 1.0].as_ref());
+
+        let knots = averaged_knots(&[0.0, 0.1, 0.4, 0.5, 0.9, 1.0], 2);
+        assert_relative_eq!(
+            knots.as_slice(),
+            [0.0, 0.0, 0.0, 0.25, 0.45, 0.7, 1.0, 1.0, 1.0].as_ref()
+        );
+    }
+
+    /// Invalid interpolation problems.
+    #[test]
+    fn interpolate_errors() {
+        let points = helix_points();
+        assert_eq!(
+            Curve::interpolate(&points[..3], 3, Parameterization::ChordLength),
+            Err(CurveError::InsufficientControlPoints {
+                degree: 3,
+                number_supplied: 3
+            })
+        );
+        assert_eq!(
+            Curve::interpolate(&points, 0, Parameterization::ChordLength),
+            Err(CurveError::InvalidDegree)
+        );
+        let repeated = [points[0], points[1], points[1], points[2]];
+        assert_eq!(
+            Curve::interpolate(&repeated, 2, Parameterization::Centripetal),
+            Err(CurveError::CoincidentPoints)
+        );
+    }
+}
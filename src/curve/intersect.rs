@@ -0,0 +1,1011 @@
+use super::bernstein::{bernstein_eval, bernstein_roots};
+use super::{Curve, CurveError, Result};
+use crate::algebra::{ScalarT, VectorT};
+use crate::bounds::BoundingBox;
+use nalgebra::Vector2;
+
+/// Maximum depth of recursive subdivision when searching for intersections.
+const MAX_SUBDIVISION_DEPTH: usize = 64;
+
+/// Size of the pieces of curve, as a fraction of the size of the two curves,
+/// below which subdivision stops and intersections are refined by Newton
+/// iteration.
+const LEAF_FRACTION: f64 = 1e-3;
+
+/// Number of points sampled along a piece of curve to check whether it lies
+/// on another curve.
+const OVERLAP_SAMPLES: usize = 5;
+
+/// Size of a piece of curve, as a multiple of the tolerance, below which it
+/// is too small to be classified as an overlap. Smaller pieces which lie
+/// within the tolerance of the other curve are refined as crossings instead.
+const MIN_OVERLAP_SIZE: f64 = 10.0;
+
+/// Maximum number of Newton iterations when refining an intersection.
+const MAX_NEWTON_ITERATIONS: usize = 100;
+
+/// Maximum number of step halvings when a Newton step does not reduce the
+/// distance between the curves.
+const MAX_STEP_HALVINGS: usize = 30;
+
+/// Separation of parameter values, as a fraction of the parameter range of
+/// a curve, below which a self-intersection is considered to be trivial
+/// (a point intersecting itself).
+const TRIVIAL_SEPARATION: f64 = 1e-9;
+
+/// Relative damping added to the Newton system, so that it remains solvable
+/// when the tangents of the curves are parallel.
+const NEWTON_DAMPING: f64 = 1e-12;
+
+/// Intersection between two curves.
+///
+/// Parameter values `u` refer to the first curve (the one on which
+/// `intersect` was called) and parameter values `v` refer to the second.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CurveIntersection<N> {
+    /// The curves cross at a single point.
+    Crossing { u: N, v: N },
+
+    /// The curves touch at a single point, where their tangents are
+    /// parallel.
+    Tangent { u: N, v: N },
+
+    /// The curves coincide along a section. The start and end of the section
+    /// are at `u.0` and `u.1` on the first curve, which correspond to `v.0`
+    /// and `v.1` on the second curve. If the curves run in opposite
+    /// directions, `v.0` is greater than `v.1`.
+    Overlap { u: (N, N), v: (N, N) },
+}
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Finds all intersections between this curve and another curve.
+    ///
+    /// Both curves are decomposed into Bézier segments, and pairs of
+    /// segments are recursively subdivided, discarding pairs whose bounding
+    /// boxes do not overlap (since each piece lies within the convex hull of
+    /// its control points). When the pieces become small, candidate
+    /// intersections are refined by Newton iteration on
+    /// `C1(u) - C2(v) = 0`.
+    ///
+    /// Intersections where the angle between the tangents of the curves is
+    /// less than `tolerance.sqrt()` (in radians) are reported as tangential.
+    /// Sections where one curve lies within `tolerance` of the other, and
+    /// runs parallel to it, are reported as overlaps. The results are sorted
+    /// by their parameter on this curve.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - the curve to intersect with this curve
+    /// * `tolerance` - the maximum distance between the curves at an
+    ///   intersection
+    pub fn intersect(&self, other: &Self, tolerance: N) -> Vec<CurveIntersection<N>> {
+        let self_segments = self.to_bezier_segments();
+        let other_segments = other.to_bezier_segments();
+        let size = BoundingBox::from_points(
+            self.control_points
+                .iter()
+                .chain(other.control_points.iter()),
+        )
+        .diagonal();
+        let leaf_size = (size * N::from_f64(LEAF_FRACTION).unwrap()).max(tolerance);
+
+        let mut search = IntersectionSearch {
+            first: self,
+            second: other,
+            tolerance,
+            leaf_size,
+            points: Vec::new(),
+            overlaps: Vec::new(),
+        };
+        for a in &self_segments {
+            for b in &other_segments {
+                search.subdivide(a, b, 0);
+            }
+        }
+        search.into_intersections()
+    }
+
+    /// Finds all points where the curve intersects itself.
+    ///
+    /// Returns pairs of parameter values `(u, v)`, with `u < v`, where
+    /// `C(u)` and `C(v)` are the same point (within `tolerance`). For a
+    /// closed curve, the pair formed by the start and end of the curve is
+    /// not reported. If a section of the curve retraces another section,
+    /// the pairs at the ends of the retraced section are reported.
+    ///
+    /// The search is the same as for `intersect`, except that pieces of the
+    /// curve which are next to each other are only subdivided until they are
+    /// monotonic along some direction, at which point they cannot intersect
+    /// other than where they join.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the maximum distance between the two points of an
+    ///   intersection
+    pub fn self_intersections(&self, tolerance: N) -> Vec<(N, N)> {
+        let segments = self.to_bezier_segments();
+        let size = self.control_bounding_box().diagonal();
+        let leaf_size = (size * N::from_f64(LEAF_FRACTION).unwrap()).max(tolerance);
+
+        let mut search = IntersectionSearch {
+            first: self,
+            second: self,
+            tolerance,
+            leaf_size,
+            points: Vec::new(),
+            overlaps: Vec::new(),
+        };
+        let (min_u, max_u) = (self.min_u(), self.max_u());
+        let closed = (self.de_boor(min_u) - self.de_boor(max_u)).norm() <= tolerance;
+        let last = segments.len() - 1;
+        for (i, a) in segments.iter().enumerate() {
+            search.subdivide_single(a, 0);
+            for (j, b) in segments.iter().enumerate().skip(i + 1) {
+                if closed && i == 0 && j == last {
+                    // the end of a closed curve joins its start
+                    search.subdivide_adjacent(b, a, 0);
+                } else if j == i + 1 {
+                    search.subdivide_adjacent(a, b, 0);
+                } else {
+                    search.subdivide(a, b, 0);
+                }
+            }
+        }
+
+        let trivial_separation = (max_u - min_u) * N::from_f64(TRIVIAL_SEPARATION).unwrap();
+
+        let mut pairs: Vec<(N, N)> = Vec::new();
+        for intersection in search.into_intersections() {
+            let candidates = match intersection {
+                CurveIntersection::Crossing { u, v } | CurveIntersection::Tangent { u, v } => {
+                    vec![(u, v)]
+                }
+                CurveIntersection::Overlap { u, v } => vec![(u.0, v.0), (u.1, v.1)],
+            };
+            for (u, v) in candidates {
+                let (u, v) = if u <= v { (u, v) } else { (v, u) };
+                let is_closure =
+                    closed && u - min_u <= trivial_separation && max_u - v <= trivial_separation;
+                let is_trivial = v - u <= trivial_separation;
+                let is_duplicate = pairs.iter().any(|&(pu, pv)| {
+                    (pu - u).abs() <= trivial_separation && (pv - v).abs() <= trivial_separation
+                });
+                if !is_closure && !is_trivial && !is_duplicate {
+                    pairs.push((u, v));
+                }
+            }
+        }
+        pairs.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        pairs
+    }
+
+    /// Refines an intersection between two curves using damped Newton
+    /// iteration, starting from parameters `s` on this curve and `t` on the
+    /// other curve.
+    ///
+    /// Returns the refined parameters and the distance between the curves at
+    /// those parameters. The iteration continues until the distance no
+    /// longer decreases, so that intersections are found as accurately as
+    /// possible.
+    fn refine_intersection(&self, other: &Self, mut s: N, mut t: N) -> (N, N, N) {
+        let half = N::from_f64(0.5).unwrap();
+        let mut distance = (self.de_boor(s) - other.de_boor(t)).norm();
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            if distance == N::zero() {
+                break;
+            }
+            let a = self.derivatives(s, 1);
+            let b = other.derivatives(t, 1);
+            let diff = a[0].clone() - b[0].clone();
+
+            // normal equations of the linearized system
+            //   C1'(s) ds - C2'(t) dt = -(C1(s) - C2(t))
+            let damping =
+                (a[1].dot(&a[1]) + b[1].dot(&b[1])) * N::from_f64(NEWTON_DAMPING).unwrap();
+            let m11 = a[1].dot(&a[1]) + damping;
+            let m12 = -a[1].dot(&b[1]);
+            let m22 = b[1].dot(&b[1]) + damping;
+            let r1 = -a[1].dot(&diff);
+            let r2 = b[1].dot(&diff);
+            let det = m11 * m22 - m12 * m12;
+            if det <= N::zero() {
+                break;
+            }
+            let mut ds = (r1 * m22 - r2 * m12) / det;
+            let mut dt = (m11 * r2 - m12 * r1) / det;
+
+            // shorten the step until it reduces the distance
+            let mut improved = false;
+            for _ in 0..MAX_STEP_HALVINGS {
+                let new_s = self.clamp_param(s + ds);
+                let new_t = other.clamp_param(t + dt);
+                let new_distance = (self.de_boor(new_s) - other.de_boor(new_t)).norm();
+                if new_distance < distance {
+                    s = new_s;
+                    t = new_t;
+                    distance = new_distance;
+                    improved = true;
+                    break;
+                }
+                ds *= half;
+                dt *= half;
+            }
+            if !improved {
+                break;
+            }
+        }
+        (s, t, distance)
+    }
+
+    /// Finds all parameter values where the curve meets a plane.
+    ///
+    /// The plane passes through `origin` and is perpendicular to `normal`.
+    /// For a 2D curve, the "plane" is a line; see also `intersect_line`.
+    ///
+    /// The signed distance of the curve from the plane is a rational
+    /// function, whose numerator is a non-rational B-spline with the signed
+    /// distances of the homogeneous control points as its coefficients.
+    /// The roots of this B-spline are found in each Bézier segment by
+    /// subdivision, using the variation-diminishing property of Bernstein
+    /// polynomials to isolate them, and are then refined by Newton iteration
+    /// safeguarded by bisection. Points where the curve touches the plane
+    /// without crossing it (within `tolerance`) are found as the stationary
+    /// points of the signed distance. If a Bézier segment of the curve lies
+    /// within `tolerance` of the plane, the ends of that segment are
+    /// returned.
+    ///
+    /// The parameter values are returned in increasing order.
+    ///
+    /// # Parameters
+    ///
+    /// * `origin` - a point on the plane
+    /// * `normal` - a vector perpendicular to the plane (need not be of
+    ///   unit length)
+    /// * `tolerance` - the maximum distance from the plane at which the curve
+    ///   is considered to touch it; roots closer together than this are
+    ///   merged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::Curve;
+    /// # use capstan::knotvec::KnotVec;
+    /// use nalgebra::Vector3;
+    ///
+    /// let curve = Curve::new(
+    ///     2,
+    ///     vec![
+    ///         Vector3::new(0.0, 0.0, -1.0),
+    ///         Vector3::new(1.0, 0.0, 3.0),
+    ///         Vector3::new(2.0, 0.0, -1.0),
+    ///     ],
+    ///     vec![1.0, 1.0, 1.0],
+    ///     KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// // z(u) = -1 + 8u - 8u^2, which is zero at u = (2 ± √2) / 4
+    /// let roots = curve
+    ///     .intersect_plane(&Vector3::zeros(), &Vector3::z(), 1e-9)
+    ///     .unwrap();
+    /// assert_eq!(roots.len(), 2);
+    /// assert!((roots[0] - (2.0 - 2.0f64.sqrt()) / 4.0).abs() < 1e-12);
+    /// assert!((roots[1] - (2.0 + 2.0f64.sqrt()) / 4.0).abs() < 1e-12);
+    /// ```
+    pub fn intersect_plane(&self, origin: &V, normal: &V, tolerance: N) -> Result<Vec<N>> {
+        let length = normal.norm();
+        if length == N::zero() {
+            return Err(CurveError::DegenerateDirection);
+        }
+        let normal = normal.clone() * (N::one() / length);
+        let offset = origin.dot(&normal);
+
+        let mut roots = Vec::new();
+        let breakpoints = self.breakpoints();
+        for (segment, span) in self
+            .bezier_decomposition()
+            .iter()
+            .zip(breakpoints.windows(2))
+        {
+            let (a, b) = (span[0], span[1]);
+            let distances: Vec<N> = segment
+                .iter()
+                .map(|h| h.point.dot(&normal) - h.weight * offset)
+                .collect();
+            let weights: Vec<N> = segment.iter().map(|h| h.weight).collect();
+            let to_u = |t: N| a + (b - a) * t;
+
+            // by the convex hull property, the whole segment lies within the
+            // tolerance of the plane if all of its control points do
+            if distances
+                .iter()
+                .zip(&weights)
+                .all(|(&d, &w)| d.abs() <= tolerance * w)
+            {
+                roots.push(a);
+                roots.push(b);
+                continue;
+            }
+
+            // crossings are the roots of the signed distance
+            roots.extend(bernstein_roots(&distances, N::zero()).into_iter().map(to_u));
+
+            // touching points are stationary points of the signed distance
+            // which lie within the tolerance of the plane
+            let derivative: Vec<N> = distances.windows(2).map(|w| w[1] - w[0]).collect();
+            for t in bernstein_roots(&derivative, N::zero()) {
+                let distance = bernstein_eval(&distances, t) / bernstein_eval(&weights, t);
+                if distance.abs() <= tolerance {
+                    roots.push(to_u(t));
+                }
+            }
+        }
+
+        // roots are merged by their parameters, so that distinct roots at the
+        // same point (where the curve crosses itself) are kept; the
+        // tolerance is converted to a parameter distance using the size of
+        // the curve relative to its parameter range
+        let range = self.max_u() - self.min_u();
+        let size = self.control_bounding_box().diagonal();
+        let parameter_tolerance = if size > tolerance {
+            range * tolerance / size
+        } else {
+            range
+        };
+        roots.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        let mut merged: Vec<N> = Vec::with_capacity(roots.len());
+        for u in roots {
+            match merged.last() {
+                Some(&last) if u - last <= parameter_tolerance => {}
+                _ => merged.push(u),
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Checks whether this curve lies along another curve, by sampling
+    /// points along it.
+    ///
+    /// The curve lies along the other curve if it is much larger than
+    /// `tolerance`, and each sampled point lies within `tolerance` of the
+    /// other curve, where the tangents of the curves are parallel (using the
+    /// same angle tolerance as `intersect`). If it does, returns the
+    /// parameters on the other curve which correspond to the start and end
+    /// of this curve.
+    ///
+    /// Points are only sampled once the control polygon of this curve lies
+    /// within `tolerance` of the bounding box of the other curve's control
+    /// polygon. Pieces which fail this check are subdivided further, until
+    /// their control polygons are close enough to the curve to pass it.
+    fn lies_within(&self, other: &Self, tolerance: N) -> Option<(N, N)> {
+        let min_size = tolerance * N::from_f64(MIN_OVERLAP_SIZE).unwrap();
+        if self.control_bounding_box().diagonal() <= min_size {
+            return None;
+        }
+        let bounds = other.control_bounding_box();
+        let inside = self.control_points.iter().all(|p| {
+            (0..p.dimension())
+                .all(|i| p[i] >= bounds.min()[i] - tolerance && p[i] <= bounds.max()[i] + tolerance)
+        });
+        if !inside {
+            return None;
+        }
+
+        let angle_tolerance = tolerance.sqrt();
+        let (min_u, max_u) = (self.min_u(), self.max_u());
+        let n = N::from_usize(OVERLAP_SAMPLES - 1).unwrap();
+        let mut params = Vec::with_capacity(OVERLAP_SAMPLES);
+        for i in 0..OVERLAP_SAMPLES {
+            let u = min_u + (max_u - min_u) * N::from_usize(i).unwrap() / n;
+            let (v, _, distance) = other.closest_point(&self.de_boor(u), tolerance);
+            if distance > tolerance {
+                return None;
+            }
+            let a = self.derivatives(u, 1);
+            let b = other.derivatives(v, 1);
+            if !are_parallel(&a[1], &b[1], angle_tolerance) {
+                return None;
+            }
+            params.push(v);
+        }
+        Some((params[0], params[OVERLAP_SAMPLES - 1]))
+    }
+}
+
+/// State of a search for the intersections between two curves.
+struct IntersectionSearch<'a, N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    first: &'a Curve<N, V>,
+    second: &'a Curve<N, V>,
+    tolerance: N,
+    leaf_size: N,
+    /// Refined point intersections, as `(u, v, distance)`.
+    points: Vec<(N, N, N)>,
+    /// Overlapping sections, as `((u0, u1), (v0, v1))`.
+    overlaps: Vec<((N, N), (N, N))>,
+}
+
+impl<'a, N, V> IntersectionSearch<'a, N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Searches for intersections between a piece of the first curve and a
+    /// piece of the second curve.
+    fn subdivide(&mut self, a: &Curve<N, V>, b: &Curve<N, V>, depth: usize) {
+        let a_bounds = a.control_bounding_box();
+        let b_bounds = b.control_bounding_box();
+        if !a_bounds.overlaps(&b_bounds, self.tolerance) {
+            return;
+        }
+
+        // pieces which lie along the other curve are overlaps; pieces which
+        // are too small, or cross the other curve, are refined below instead
+        if let Some(v) = a.lies_within(b, self.tolerance) {
+            let u = (a.min_u(), a.max_u());
+            self.overlaps.push((u, v));
+            return;
+        }
+        if let Some(u) = b.lies_within(a, self.tolerance) {
+            let v = (b.min_u(), b.max_u());
+            self.overlaps.push((u, v));
+            return;
+        }
+
+        let (a_size, b_size) = (a_bounds.diagonal(), b_bounds.diagonal());
+        if depth >= MAX_SUBDIVISION_DEPTH || (a_size <= self.leaf_size && b_size <= self.leaf_size)
+        {
+            let two = N::from_f64(2.0).unwrap();
+            let s = (a.min_u() + a.max_u()) / two;
+            let t = (b.min_u() + b.max_u()) / two;
+            let refined = self.first.refine_intersection(self.second, s, t);
+            if refined.2 <= self.tolerance {
+                self.points.push(refined);
+            }
+            return;
+        }
+
+        // split the larger piece
+        if a_size >= b_size {
+            let (a0, a1) = split_in_half(a);
+            self.subdivide(&a0, b, depth + 1);
+            self.subdivide(&a1, b, depth + 1);
+        } else {
+            let (b0, b1) = split_in_half(b);
+            self.subdivide(a, &b0, depth + 1);
+            self.subdivide(a, &b1, depth + 1);
+        }
+    }
+
+    /// Searches for self-intersections within a single piece of the curve.
+    fn subdivide_single(&mut self, a: &Curve<N, V>, depth: usize) {
+        if depth >= MAX_SUBDIVISION_DEPTH || is_monotonic(a.control_points.iter()) {
+            return;
+        }
+        let (a0, a1) = split_in_half(a);
+        self.subdivide_single(&a0, depth + 1);
+        self.subdivide_single(&a1, depth + 1);
+        self.subdivide_adjacent(&a0, &a1, depth + 1);
+    }
+
+    /// Searches for intersections between two pieces of a curve, where the
+    /// end of the first piece joins the start of the second piece.
+    ///
+    /// The bounding boxes of the pieces always overlap where they join, so
+    /// instead the pieces are subdivided until they are monotonic together,
+    /// in which case they only meet at the join.
+    fn subdivide_adjacent(&mut self, a: &Curve<N, V>, b: &Curve<N, V>, depth: usize) {
+        let joined = a
+            .control_points
+            .iter()
+            .chain(b.control_points.iter().skip(1));
+        if depth >= MAX_SUBDIVISION_DEPTH || is_monotonic(joined) {
+            return;
+        }
+        if a.control_bounding_box().diagonal() >= b.control_bounding_box().diagonal() {
+            let (a0, a1) = split_in_half(a);
+            self.subdivide(&a0, b, depth + 1);
+            self.subdivide_adjacent(&a1, b, depth + 1);
+        } else {
+            let (b0, b1) = split_in_half(b);
+            self.subdivide_adjacent(a, &b0, depth + 1);
+            self.subdivide(a, &b1, depth + 1);
+        }
+    }
+
+    /// Merges overlaps and duplicate points, classifies the points, and
+    /// returns the sorted intersections.
+    fn into_intersections(mut self) -> Vec<CurveIntersection<N>> {
+        let merge_distance = self.tolerance.sqrt().max(self.tolerance);
+        let first = self.first;
+        let second = self.second;
+        let same_point_on_first =
+            |u0: N, u1: N| (first.de_boor(u0) - first.de_boor(u1)).norm() <= merge_distance;
+
+        // merge overlaps whose ranges on the first curve touch
+        let mut overlaps: Vec<((N, N), (N, N))> = Vec::new();
+        self.overlaps
+            .sort_by(|x, y| x.0 .0.min(x.0 .1).partial_cmp(&y.0 .0.min(y.0 .1)).unwrap());
+        for ((u0, u1), (v0, v1)) in self.overlaps {
+            let (u0, u1, v0, v1) = if u0 <= u1 {
+                (u0, u1, v0, v1)
+            } else {
+                (u1, u0, v1, v0)
+            };
+            match overlaps.last_mut() {
+                Some(((_, last_u1), (_, last_v1)))
+                    if u0 <= *last_u1 || same_point_on_first(u0, *last_u1) =>
+                {
+                    if u1 > *last_u1 {
+                        *last_u1 = u1;
+                        *last_v1 = v1;
+                    }
+                }
+                _ => overlaps.push(((u0, u1), (v0, v1))),
+            }
+        }
+
+        // discard points within overlaps, and duplicate points
+        self.points.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+        let mut points: Vec<(N, N, N)> = Vec::new();
+        for (u, v, distance) in self.points {
+            let in_overlap = overlaps.iter().any(|&((u0, u1), _)| {
+                (u >= u0 && u <= u1) || same_point_on_first(u, u0) || same_point_on_first(u, u1)
+            });
+            if in_overlap {
+                continue;
+            }
+            match points.iter_mut().find(|p| {
+                same_point_on_first(p.0, u)
+                    && (second.de_boor(p.1) - second.de_boor(v)).norm() <= merge_distance
+            }) {
+                Some(p) if distance < p.2 => *p = (u, v, distance),
+                Some(_) => {}
+                None => points.push((u, v, distance)),
+            }
+        }
+
+        let angle_tolerance = self.tolerance.sqrt();
+        let mut intersections: Vec<CurveIntersection<N>> = points
+            .into_iter()
+            .map(|(u, v, _)| {
+                let a = first.derivatives(u, 1);
+                let b = second.derivatives(v, 1);
+                if are_parallel(&a[1], &b[1], angle_tolerance) {
+                    CurveIntersection::Tangent { u, v }
+                } else {
+                    CurveIntersection::Crossing { u, v }
+                }
+            })
+            .chain(
+                overlaps
+                    .into_iter()
+                    .map(|(u, v)| CurveIntersection::Overlap { u, v }),
+            )
+            .collect();
+        intersections.sort_by(|x, y| start_u(x).partial_cmp(&start_u(y)).unwrap());
+        intersections
+    }
+}
+
+impl<N> Curve<N, Vector2<N>>
+where
+    N: 'static + ScalarT,
+{
+    /// Finds all parameter values where a 2D curve meets an infinite line.
+    ///
+    /// The line passes through `point` in the direction of `direction`. See
+    /// `intersect_plane` for details of the method.
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - a point on the line
+    /// * `direction` - the direction of the line (need not be of unit
+    ///   length)
+    /// * `tolerance` - the maximum distance from the line at which the curve
+    ///   is considered to touch it; roots closer together than this are
+    ///   merged
+    pub fn intersect_line(
+        &self,
+        point: &Vector2<N>,
+        direction: &Vector2<N>,
+        tolerance: N,
+    ) -> Result<Vec<N>> {
+        let normal = Vector2::new(-direction.y, direction.x);
+        self.intersect_plane(point, &normal, tolerance)
+    }
+}
+
+/// Checks whether a control polygon is monotonic along the direction from
+/// its first point to its last point.
+///
+/// A rational Bézier curve (with positive weights) whose control polygon is
+/// monotonic along some direction is itself strictly monotonic along that
+/// direction, and so cannot intersect itself.
+fn is_monotonic<'a, N, V>(points: impl Iterator<Item = &'a V> + Clone) -> bool
+where
+    N: ScalarT,
+    V: 'a + VectorT<Field = N>,
+{
+    let first = points.clone().next();
+    let last = points.clone().last();
+    let direction = match (first, last) {
+        (Some(first), Some(last)) => last.clone() - first.clone(),
+        _ => return true,
+    };
+    if direction.dot(&direction) == N::zero() {
+        return false;
+    }
+    points
+        .clone()
+        .zip(points.skip(1))
+        .all(|(p, q)| (q.clone() - p.clone()).dot(&direction) >= N::zero())
+}
+
+/// Checks whether the angle between two tangent vectors (in either
+/// direction) is at most `angle_tolerance` radians. A zero tangent is
+/// parallel to every direction.
+fn are_parallel<N, V>(a: &V, b: &V, angle_tolerance: N) -> bool
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let denom = a.norm() * b.norm();
+    if denom == N::zero() {
+        return true;
+    }
+    let cos = a.dot(b) / denom;
+    (N::one() - cos * cos).max(N::zero()).sqrt() <= angle_tolerance
+}
+
+/// Splits a curve in half at the middle of its parameter range.
+fn split_in_half<N, V>(curve: &Curve<N, V>) -> (Curve<N, V>, Curve<N, V>)
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let u = (curve.min_u() + curve.max_u()) / N::from_f64(2.0).unwrap();
+    curve
+        .split_at(u)
+        .expect("the middle of a curve must be inside its parameter range")
+}
+
+/// Returns the first parameter value of an intersection on the first curve.
+fn start_u<N: ScalarT>(intersection: &CurveIntersection<N>) -> N {
+    match *intersection {
+        CurveIntersection::Crossing { u, .. } | CurveIntersection::Tangent { u, .. } => u,
+        CurveIntersection::Overlap { u, .. } => u.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{reed_leaf, unit_circle, TC64};
+    use super::*;
+    use crate::knotvec::KnotVec;
+    use approx::assert_relative_eq;
+    use nalgebra::{Similarity2, Vector2, Vector3};
+
+    /// The cubic Bézier curve from the examples, which contains a loop.
+    fn cubic_bezier() -> TC64 {
+        TC64::new(
+            3,
+            vec![
+                Vector2::new(80.0, 20.0),
+                Vector2::new(280.0, 280.0),
+                Vector2::new(20.0, 280.0),
+                Vector2::new(220.0, 20.0),
+            ],
+            vec![1.0; 4],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// A straight line between two points.
+    fn line(a: Vector2<f64>, b: Vector2<f64>) -> TC64 {
+        TC64::new(
+            1,
+            vec![a, b],
+            vec![1.0; 2],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Checks that an intersection is a point where the curves meet.
+    fn assert_meets(a: &TC64, b: &TC64, intersection: &CurveIntersection<f64>) {
+        match *intersection {
+            CurveIntersection::Crossing { u, v } | CurveIntersection::Tangent { u, v } => {
+                assert_relative_eq!(a.de_boor(u), b.de_boor(v), epsilon = 1e-9);
+            }
+            CurveIntersection::Overlap { .. } => panic!("unexpected overlap"),
+        }
+    }
+
+    /// Two unit circles with centres one unit apart cross at two points.
+    #[test]
+    fn intersect_circles() {
+        let a = unit_circle();
+        let mut b = unit_circle();
+        b.transform(&Similarity2::new(Vector2::new(1.0, 0.0), 0.0, 1.0).to_homogeneous())
+            .unwrap();
+
+        let intersections = a.intersect(&b, 1e-9);
+        assert_eq!(intersections.len(), 2);
+        let y = f64::sqrt(3.0) / 2.0;
+        let expected = [Vector2::new(0.5, y), Vector2::new(0.5, -y)];
+        for (intersection, expected) in intersections.iter().zip(expected.iter()) {
+            assert!(matches!(intersection, CurveIntersection::Crossing { .. }));
+            assert_meets(&a, &b, intersection);
+            if let CurveIntersection::Crossing { u, .. } = *intersection {
+                assert_relative_eq!(a.de_boor(u), expected, epsilon = 1e-9);
+            }
+        }
+    }
+
+    /// A circle and a line which touches it.
+    #[test]
+    fn intersect_circle_tangent_line() {
+        let circle = unit_circle();
+        let tangent = line(Vector2::new(-2.0, 1.0), Vector2::new(2.0, 1.0));
+        let intersections = circle.intersect(&tangent, 1e-9);
+        assert_eq!(intersections.len(), 1);
+        match intersections[0] {
+            CurveIntersection::Tangent { u, v } => {
+                assert_relative_eq!(circle.de_boor(u), Vector2::new(0.0, 1.0), epsilon = 1e-6);
+                assert_relative_eq!(v, 0.5, epsilon = 1e-6);
+            }
+            ref other => panic!("expected a tangent intersection; found {:?}", other),
+        }
+
+        // a line which misses the circle
+        let miss = line(Vector2::new(-2.0, 1.1), Vector2::new(2.0, 1.1));
+        assert!(circle.intersect(&miss, 1e-9).is_empty());
+    }
+
+    /// The cubic Bézier example crossed by a horizontal line.
+    #[test]
+    fn intersect_cubic_line() {
+        let cubic = cubic_bezier();
+        let horizontal = line(Vector2::new(0.0, 150.0), Vector2::new(300.0, 150.0));
+        let intersections = cubic.intersect(&horizontal, 1e-9);
+        assert_eq!(intersections.len(), 2);
+        for intersection in &intersections {
+            assert!(matches!(intersection, CurveIntersection::Crossing { .. }));
+            assert_meets(&cubic, &horizontal, intersection);
+        }
+
+        // the middle of the cubic passes out of, and back into, a circle
+        // around its centre
+        let mut circle = unit_circle();
+        circle
+            .transform(&Similarity2::new(Vector2::new(150.0, 150.0), 0.0, 60.0).to_homogeneous())
+            .unwrap();
+        let intersections = cubic.intersect(&circle, 1e-9);
+        assert_eq!(intersections.len(), 4);
+        for intersection in &intersections {
+            assert_meets(&cubic, &circle, intersection);
+        }
+    }
+
+    /// Part of a circle overlaps the whole circle.
+    #[test]
+    fn intersect_overlap() {
+        let circle = unit_circle();
+        let arc = circle.subcurve(0.1, 0.6).unwrap().reversed();
+        let intersections = circle.intersect(&arc, 1e-9);
+        assert_eq!(intersections.len(), 1);
+        match intersections[0] {
+            CurveIntersection::Overlap { u, v } => {
+                assert_relative_eq!(u.0, 0.1, epsilon = 1e-6);
+                assert_relative_eq!(u.1, 0.6, epsilon = 1e-6);
+                assert_relative_eq!(v.0, 0.6, epsilon = 1e-6);
+                assert_relative_eq!(v.1, 0.1, epsilon = 1e-6);
+            }
+            ref other => panic!("expected an overlap; found {:?}", other),
+        }
+    }
+
+    /// Lines which cross at nearly a right angle are reported as a crossing,
+    /// even when the tolerance is larger than the pieces which lie within it
+    /// of the other line.
+    #[test]
+    fn intersect_crossing_not_overlap() {
+        let a = line(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0));
+        let b = line(Vector2::new(5.0, -5.0), Vector2::new(5.01, 5.0));
+        for &tolerance in &[1e-3, 1e-2] {
+            let intersections = a.intersect(&b, tolerance);
+            assert_eq!(intersections.len(), 1);
+            match intersections[0] {
+                CurveIntersection::Crossing { u, v } => {
+                    assert_relative_eq!(u, 0.5005, epsilon = 1e-9);
+                    assert_relative_eq!(v, 0.5, epsilon = 1e-9);
+                }
+                ref other => panic!("expected a crossing; found {:?}", other),
+            }
+        }
+    }
+
+    /// Crossings of the circle and the cubic example with lines.
+    #[test]
+    fn intersect_line() {
+        let circle = unit_circle();
+        let roots = circle
+            .intersect_line(&Vector2::new(0.0, 0.5), &Vector2::new(2.0, 0.0), 1e-9)
+            .unwrap();
+        assert_eq!(roots.len(), 2);
+        let x = f64::sqrt(3.0) / 2.0;
+        assert_relative_eq!(
+            circle.de_boor(roots[0]),
+            Vector2::new(x, 0.5),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            circle.de_boor(roots[1]),
+            Vector2::new(-x, 0.5),
+            epsilon = 1e-12
+        );
+
+        // a line through the centre crosses at the seam of the circle
+        let roots = circle
+            .intersect_line(&Vector2::zeros(), &Vector2::new(1.0, 0.0), 1e-9)
+            .unwrap();
+        assert_eq!(roots, vec![0.0, 0.5, 1.0]);
+
+        // a tangent line touches the circle once
+        let roots = circle
+            .intersect_line(&Vector2::new(0.0, -1.0), &Vector2::new(1.0, 0.0), 1e-9)
+            .unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_relative_eq!(roots[0], 0.75, epsilon = 1e-9);
+
+        // the loop of the cubic crosses a vertical line three times
+        let cubic = cubic_bezier();
+        let roots = cubic
+            .intersect_line(&Vector2::new(150.0, 0.0), &Vector2::new(0.0, 1.0), 1e-9)
+            .unwrap();
+        assert_eq!(roots.len(), 3);
+        for u in roots {
+            assert_relative_eq!(cubic.de_boor(u).x, 150.0, epsilon = 1e-9);
+        }
+
+        assert_eq!(
+            circle.intersect_line(&Vector2::zeros(), &Vector2::zeros(), 1e-9),
+            Err(CurveError::DegenerateDirection)
+        );
+    }
+
+    /// Crossings of a rational 3D curve with a plane, and a line lying in
+    /// the plane.
+    #[test]
+    fn intersect_plane() {
+        let knots = KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 3.0]).unwrap();
+        let curve = Curve::<f64, Vector3<f64>>::new(
+            2,
+            vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(1.0, 1.0, -1.0),
+                Vector3::new(0.0, 1.0, 1.0),
+                Vector3::new(0.0, 2.0, -1.0),
+            ],
+            vec![1.0, 2.0, 1.0, 0.5, 1.0],
+            knots,
+        )
+        .unwrap();
+        let normal = Vector3::new(0.0, 0.0, 3.0);
+        let roots = curve
+            .intersect_plane(&Vector3::zeros(), &normal, 1e-9)
+            .unwrap();
+        assert_eq!(roots[0], 0.0);
+
+        // compare against sign changes of densely-sampled points
+        let n = 3000;
+        let sign_changes = (0..n)
+            .filter(|&i| {
+                let z0 = curve.de_boor(3.0 * (i as f64) / (n as f64)).z;
+                let z1 = curve.de_boor(3.0 * ((i + 1) as f64) / (n as f64)).z;
+                z0 * z1 < 0.0
+            })
+            .count();
+        // the curve starts on the plane, and touches it at u = 7/3
+        assert_eq!(roots.len(), sign_changes + 2);
+        assert_relative_eq!(roots[2], 7.0 / 3.0, epsilon = 1e-6);
+        for &u in &roots {
+            assert_relative_eq!(curve.de_boor(u).z, 0.0, epsilon = 1e-12);
+        }
+
+        // a straight line lying in the plane
+        let flat = Curve::<f64, Vector3<f64>>::new(
+            1,
+            vec![Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0)],
+            vec![1.0, 1.0],
+            KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            flat.intersect_plane(&Vector3::zeros(), &normal, 1e-9)
+                .unwrap(),
+            vec![0.0, 1.0]
+        );
+
+        // rounding errors do not stop a line being found to lie in the plane
+        let offset = Vector3::new(0.0, 0.0, 1e-12);
+        assert_eq!(
+            flat.intersect_plane(&offset, &normal, 1e-9).unwrap(),
+            vec![0.0, 1.0]
+        );
+    }
+
+    /// Roots at the same point but different parameters are not merged.
+    #[test]
+    fn intersect_line_through_self_intersection() {
+        let cubic = cubic_bezier();
+        let (u, v) = cubic.self_intersections(1e-9)[0];
+        let point = cubic.de_boor(u);
+        let roots = cubic
+            .intersect_line(&point, &Vector2::new(0.0, 1.0), 1e-9)
+            .unwrap();
+        assert!(roots.iter().any(|&r| (r - u).abs() < 1e-6));
+        assert!(roots.iter().any(|&r| (r - v).abs() < 1e-6));
+    }
+
+    /// The loop in the cubic example is a self-intersection, whether or not
+    /// it is split between Bézier segments.
+    #[test]
+    fn self_intersections_cubic() {
+        let cubic = cubic_bezier();
+        let split = cubic.insert_knot(0.5, 3).unwrap();
+        for curve in &[cubic, split] {
+            let pairs = curve.self_intersections(1e-9);
+            assert_eq!(pairs.len(), 1);
+            let (u, v) = pairs[0];
+            assert!(u < 0.5 && v > 0.5);
+            assert_relative_eq!(u, 1.0 - v, epsilon = 1e-9);
+            assert_relative_eq!(curve.de_boor(u), curve.de_boor(v), epsilon = 1e-9);
+        }
+    }
+
+    /// Closed curves do not intersect themselves at their closure point.
+    #[test]
+    fn self_intersections_closed() {
+        assert!(unit_circle().self_intersections(1e-9).is_empty());
+        assert!(reed_leaf().self_intersections(1e-9).is_empty());
+    }
+
+    /// A closed figure-of-eight crosses itself once, in the middle.
+    #[test]
+    fn self_intersections_figure_eight() {
+        let figure_eight = TC64::new(
+            3,
+            vec![
+                Vector2::new(-2.0, 0.0),
+                Vector2::new(-1.0, 1.0),
+                Vector2::new(1.0, -1.0),
+                Vector2::new(2.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(-1.0, -1.0),
+                Vector2::new(-2.0, 0.0),
+            ],
+            vec![1.0; 7],
+            KnotVec::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 4.0, 4.0]).unwrap(),
+        )
+        .unwrap();
+        let pairs = figure_eight.self_intersections(1e-9);
+        assert_eq!(pairs.len(), 1);
+        let (u, v) = pairs[0];
+        assert_relative_eq!(
+            figure_eight.de_boor(u),
+            figure_eight.de_boor(v),
+            epsilon = 1e-9
+        );
+    }
+}
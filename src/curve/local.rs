@@ -0,0 +1,308 @@
+use super::interpolate::{parameterize, Parameterization};
+use super::{Curve, CurveError, Result};
+use crate::algebra::{ScalarT, VectorT};
+use crate::knotvec::KnotVec;
+
+/// Method of estimating the tangent of a locally interpolating curve at each
+/// of the points it passes through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TangentEstimate {
+    /// The tangent at each point is the derivative of the parabola through
+    /// the point and its two neighbours. This reproduces quadratic data
+    /// exactly, but can overshoot where the data turns sharply.
+    Bessel,
+
+    /// The tangent at each point is a blend of the chords on either side,
+    /// weighted to favour the side where the data is straighter (Akima, "A
+    /// new method of interpolation and smooth curve fitting based on local
+    /// procedures", 1970). Straight runs of data stay straight, and the curve
+    /// does not overshoot at corners.
+    Akima,
+}
+
+impl<N, V> Curve<N, V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    /// Creates a cubic curve which passes through a sequence of points, using
+    /// only nearby points to shape each segment.
+    ///
+    /// A tangent is estimated at each point from its neighbours, and each
+    /// pair of successive points is joined by a cubic Bézier segment which
+    /// matches the tangents at both ends (section 9.3.4 of The NURBS Book).
+    /// The segments are joined with double knots, so the curve has a
+    /// continuous first derivative but, unlike the curves from `interpolate`,
+    /// not a continuous second derivative. In exchange, the curve is built in
+    /// linear time, and moving a point only changes the curve near it.
+    ///
+    /// The curve has a parameter range of `0..=1`, and passes through each
+    /// point at the parameter value chosen for it according to
+    /// `parameterization`. An error is returned if there are fewer than two
+    /// points, or if successive points coincide (for the chord length and
+    /// centripetal parameterizations).
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the points which the curve must pass through, in order
+    /// * `tangents` - the method used to estimate the tangent at each point
+    /// * `parameterization` - the method used to choose the parameter value of
+    ///   each point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use capstan::curve::{Curve, Parameterization, TangentEstimate};
+    /// use nalgebra::Vector2;
+    ///
+    /// let points = vec![
+    ///     Vector2::new(0.0f64, 0.0),
+    ///     Vector2::new(1.0, 0.0),
+    ///     Vector2::new(2.0, 0.0),
+    ///     Vector2::new(3.0, 2.0),
+    ///     Vector2::new(4.0, 2.0),
+    ///     Vector2::new(5.0, 2.0),
+    /// ];
+    /// let curve = Curve::interpolate_local(
+    ///     &points,
+    ///     TangentEstimate::Akima,
+    ///     Parameterization::ChordLength,
+    /// )
+    /// .unwrap();
+    /// for point in &points {
+    ///     let (_, _, distance) = curve.closest_point(point, 1e-12);
+    ///     assert!(distance < 1e-9);
+    /// }
+    /// // the flat run at the start does not wobble
+    /// assert!(curve.control_points()[..4].iter().all(|p| p.y.abs() < 1e-12));
+    /// ```
+    pub fn interpolate_local(
+        points: &[V],
+        tangents: TangentEstimate,
+        parameterization: Parameterization,
+    ) -> Result<Self> {
+        if points.len() < 2 {
+            return Err(CurveError::InsufficientControlPoints {
+                degree: 3,
+                number_supplied: points.len(),
+            });
+        }
+        let params = parameterize(points, parameterization)?;
+
+        // the divided difference across each chord, which is the derivative
+        // of the straight line joining its ends
+        let chords: Vec<V> = points
+            .windows(2)
+            .zip(params.windows(2))
+            .map(|(p, u)| (p[1].clone() - p[0].clone()) * (N::one() / (u[1] - u[0])))
+            .collect();
+        let derivatives = match tangents {
+            TangentEstimate::Bessel => bessel_derivatives(&params, &chords),
+            TangentEstimate::Akima => akima_derivatives(&chords),
+        };
+
+        // each segment is a Bézier curve with its inner control points a
+        // third of the way along the tangents at its ends
+        let third = N::one() / N::from_f64(3.0).unwrap();
+        let mut control_points = Vec::with_capacity(2 * points.len());
+        let mut knots = vec![N::zero(); 4];
+        control_points.push(points[0].clone());
+        for k in 0..points.len() - 1 {
+            let step = (params[k + 1] - params[k]) * third;
+            control_points.push(points[k].clone() + derivatives[k].clone() * step);
+            control_points.push(points[k + 1].clone() - derivatives[k + 1].clone() * step);
+            if k > 0 {
+                knots.extend(std::iter::repeat_n(params[k], 2));
+            }
+        }
+        control_points.push(points[points.len() - 1].clone());
+        knots.extend(std::iter::repeat_n(N::one(), 4));
+
+        let weights = vec![N::one(); control_points.len()];
+        let knots = KnotVec::new(knots).expect("local interpolation knots must be valid");
+        Curve::new(3, control_points, weights, knots)
+    }
+}
+
+/// Estimates the derivative at each point from the parabola through it and
+/// its neighbours, with the end derivatives taken from the parabolas through
+/// the first and last three points.
+fn bessel_derivatives<N, V>(params: &[N], chords: &[V]) -> Vec<V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let n = chords.len();
+    if n == 1 {
+        return vec![chords[0].clone(), chords[0].clone()];
+    }
+    let mut derivatives = Vec::with_capacity(n + 1);
+    derivatives.push(V::zero());
+    for k in 1..n {
+        let (before, after) = (params[k] - params[k - 1], params[k + 1] - params[k]);
+        let alpha = before / (before + after);
+        derivatives.push(chords[k - 1].clone() * (N::one() - alpha) + chords[k].clone() * alpha);
+    }
+    let two = N::from_f64(2.0).unwrap();
+    derivatives[0] = chords[0].clone() * two - derivatives[1].clone();
+    derivatives.push(chords[n - 1].clone() * two - derivatives[n - 1].clone());
+    derivatives
+}
+
+/// Estimates the derivative at each point by blending the chords on either
+/// side, according to how sharply the data turns before and after the point
+/// (equations 9.29 and 9.30 of The NURBS Book). Two extra chords are
+/// extrapolated at each end.
+fn akima_derivatives<N, V>(chords: &[V]) -> Vec<V>
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let n = chords.len();
+    let two = N::from_f64(2.0).unwrap();
+    let extrapolate = |a: &V, b: &V| a.clone() * two - b.clone();
+    let (first, last) = if n == 1 {
+        (chords[0].clone(), chords[0].clone())
+    } else {
+        (
+            extrapolate(&chords[0], &chords[1]),
+            extrapolate(&chords[n - 1], &chords[n - 2]),
+        )
+    };
+    let mut q = Vec::with_capacity(n + 4);
+    q.push(extrapolate(&first, &chords[0]));
+    q.push(first.clone());
+    q.extend(chords.iter().cloned());
+    q.push(last.clone());
+    q.push(extrapolate(&last, &chords[n - 1]));
+
+    // the derivative at point k lies between chords q[k + 1] and q[k + 2]
+    (0..=n)
+        .map(|k| {
+            let before = cross_norm(&q[k], &q[k + 1]);
+            let after = cross_norm(&q[k + 2], &q[k + 3]);
+            let alpha = if before + after > N::zero() {
+                before / (before + after)
+            } else {
+                N::from_f64(0.5).unwrap()
+            };
+            q[k + 1].clone() * (N::one() - alpha) + q[k + 2].clone() * alpha
+        })
+        .collect()
+}
+
+/// Returns the magnitude of the cross product of two vectors, which is the
+/// area of the parallelogram they span, in any number of dimensions.
+fn cross_norm<N, V>(a: &V, b: &V) -> N
+where
+    N: ScalarT,
+    V: VectorT<Field = N>,
+{
+    let dot = a.dot(b);
+    (a.dot(a) * b.dot(b) - dot * dot).max(N::zero()).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::helix_points;
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::Vector2;
+
+    /// Locally interpolating curves pass through every point, with
+    /// continuous tangents.
+    #[test]
+    fn interpolate_local() {
+        let points = helix_points();
+        for &tangents in &[TangentEstimate::Bessel, TangentEstimate::Akima] {
+            for &parameterization in &[
+                Parameterization::Uniform,
+                Parameterization::ChordLength,
+                Parameterization::Centripetal,
+            ] {
+                let curve = Curve::interpolate_local(&points, tangents, parameterization).unwrap();
+                assert_eq!(curve.degree(), 3);
+                assert_eq!(curve.control_points().len(), 2 * points.len());
+                let params = parameterize(&points, parameterization).unwrap();
+                for (point, &u) in points.iter().zip(&params) {
+                    assert_relative_eq!(curve.de_boor(u), point, epsilon = 1e-12);
+                }
+                for &u in &params[1..points.len() - 1] {
+                    let before = curve.derivatives(u - 1e-9, 1)[1];
+                    let after = curve.derivatives(u + 1e-9, 1)[1];
+                    assert_relative_eq!(before, after, epsilon = 1e-6);
+                }
+            }
+        }
+
+        // two points are joined by a straight line
+        let line = [Vector2::new(0.0, 0.0), Vector2::new(3.0, 3.0)];
+        for &tangents in &[TangentEstimate::Bessel, TangentEstimate::Akima] {
+            let curve =
+                Curve::interpolate_local(&line, tangents, Parameterization::Uniform).unwrap();
+            assert_relative_eq!(curve.control_points()[1], Vector2::new(1.0, 1.0));
+            assert_relative_eq!(curve.control_points()[2], Vector2::new(2.0, 2.0));
+        }
+    }
+
+    /// Bessel tangents reproduce a parabola exactly.
+    #[test]
+    fn bessel_parabola() {
+        let parabola = |t: f64| Vector2::new(t, t * t);
+        let points: Vec<Vector2<f64>> = (0..6).map(|i| parabola(i as f64 / 5.0)).collect();
+        let curve =
+            Curve::interpolate_local(&points, TangentEstimate::Bessel, Parameterization::Uniform)
+                .unwrap();
+        for i in 0..=50 {
+            let t = i as f64 / 50.0;
+            assert_relative_eq!(curve.de_boor(t), parabola(t), epsilon = 1e-12);
+        }
+    }
+
+    /// Akima tangents keep straight runs straight, where Bessel tangents
+    /// overshoot.
+    #[test]
+    fn akima_step() {
+        let points: Vec<Vector2<f64>> = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &y)| Vector2::new(i as f64, y))
+            .collect();
+        let overshoot = |tangents| {
+            let curve =
+                Curve::interpolate_local(&points, tangents, Parameterization::Uniform).unwrap();
+            (0..=100)
+                .map(|i| curve.de_boor(i as f64 / 100.0).y)
+                .map(|y: f64| (-y).max(y - 1.0))
+                .fold(0.0, f64::max)
+        };
+        assert_relative_eq!(overshoot(TangentEstimate::Akima), 0.0, epsilon = 1e-12);
+        assert!(overshoot(TangentEstimate::Bessel) > 0.01);
+    }
+
+    /// Invalid local interpolation problems.
+    #[test]
+    fn interpolate_local_errors() {
+        let points = helix_points();
+        assert_eq!(
+            Curve::interpolate_local(
+                &points[..1],
+                TangentEstimate::Akima,
+                Parameterization::ChordLength
+            ),
+            Err(CurveError::InsufficientControlPoints {
+                degree: 3,
+                number_supplied: 1
+            })
+        );
+        let repeated = [points[0], points[1], points[1], points[2]];
+        assert_eq!(
+            Curve::interpolate_local(
+                &repeated,
+                TangentEstimate::Bessel,
+                Parameterization::ChordLength
+            ),
+            Err(CurveError::CoincidentPoints)
+        );
+    }
+}
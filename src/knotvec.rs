@@ -54,6 +54,11 @@ impl<N: ScalarT> KnotVec<N> {
         self.knots.len()
     }
 
+    /// Returns the knot values as a slice.
+    pub fn as_slice(&self) -> &[N] {
+        &self.knots
+    }
+
     /// Checks if a knot vector is clamped.
     ///
     /// A knot vector is clamped if the first knot value is repeated
@@ -70,7 +75,7 @@ impl<N: ScalarT> KnotVec<N> {
         } else {
             // check the value of the start knots
             let start_knot = self.knots[0];
-            for i_knot in &self.knots[1..degree] {
+            for i_knot in &self.knots[1..degree + 1] {
                 if *i_knot != start_knot {
                     return false;
                 }
@@ -107,6 +112,90 @@ impl<N: ScalarT> KnotVec<N> {
             .expect("last() should always succeed, because there should be >=2 knots")
     }
 
+    /// Returns the multiplicity of a knot value.
+    ///
+    /// The multiplicity is the number of times that the value `u` appears in
+    /// the knot vector. It is zero if `u` is not a knot.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the knot value to find
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use capstan::knotvec::KnotVec;
+    /// let knots = KnotVec::new(vec![0.0, 0.0, 0.5, 0.5, 1.0, 1.0]).unwrap();
+    /// assert_eq!(knots.multiplicity(0.5), 2);
+    /// assert_eq!(knots.multiplicity(0.7), 0);
+    /// ```
+    pub fn multiplicity(&self, u: N) -> usize {
+        self.knots.iter().filter(|&&k| k == u).count()
+    }
+
+    /// Returns a new knot vector with a knot value inserted a number of times.
+    ///
+    /// The inserted knots are placed after any existing knots with the same
+    /// value, so that the knot vector remains in non-decreasing order.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the knot value to insert
+    /// * `times` - the number of times to insert the knot value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use capstan::knotvec::KnotVec;
+    /// let knots = KnotVec::new(vec![0.0, 0.0, 1.0, 1.0]).unwrap();
+    /// let inserted = knots.insert(0.5, 2);
+    /// assert_eq!(inserted, KnotVec::new(vec![0.0, 0.0, 0.5, 0.5, 1.0, 1.0]).unwrap());
+    /// ```
+    pub fn insert(&self, u: N, times: usize) -> Self {
+        let index = self.knots.partition_point(|&k| k <= u);
+        let mut knots = Vec::with_capacity(self.knots.len() + times);
+        knots.extend_from_slice(&self.knots[..index]);
+        knots.extend(std::iter::repeat_n(u, times));
+        knots.extend_from_slice(&self.knots[index..]);
+        KnotVec { knots }
+    }
+
+    /// Returns a new knot vector with a knot value removed a number of times.
+    ///
+    /// At most `multiplicity(u)` copies of the knot are removed. Returns
+    /// `None` if the resulting knot vector would not be valid.
+    ///
+    /// # Parameters
+    ///
+    /// * `u` - the knot value to remove
+    /// * `times` - the number of times to remove the knot value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use capstan::knotvec::KnotVec;
+    /// let knots = KnotVec::new(vec![0.0, 0.0, 0.5, 0.5, 1.0, 1.0]).unwrap();
+    /// let removed = knots.remove(0.5, 1).unwrap();
+    /// assert_eq!(removed, KnotVec::new(vec![0.0, 0.0, 0.5, 1.0, 1.0]).unwrap());
+    /// ```
+    pub fn remove(&self, u: N, times: usize) -> Option<Self> {
+        let mut remaining = times;
+        let knots = self
+            .knots
+            .iter()
+            .filter(|&&k| {
+                if k == u && remaining > 0 {
+                    remaining -= 1;
+                    false
+                } else {
+                    true
+                }
+            })
+            .copied()
+            .collect();
+        KnotVec::new(knots)
+    }
+
     /// Clamp a parameter value to the allowed range of the parameter.
     ///
     /// # Parameters
@@ -201,6 +290,110 @@ impl<N: ScalarT> KnotVec<N> {
             mid
         }
     }
+
+    /// Evaluates the non-zero B-spline basis functions and their derivatives
+    /// at a parameter value.
+    ///
+    /// Within the knot span `span`, only the `degree + 1` basis functions
+    /// `N[span - degree, degree]` to `N[span, degree]` are non-zero. This
+    /// method returns a table, `ders`, where `ders[k][j]` is the `k`th
+    /// derivative of the basis function `N[span - degree + j, degree]`
+    /// evaluated at `u`. Row `k = 0` contains the basis functions themselves.
+    /// Derivatives of order greater than `degree` are identically zero.
+    ///
+    /// This is algorithm A2.3 from The NURBS Book (Piegl and Tiller).
+    ///
+    /// # Parameters
+    ///
+    /// * `degree` - degree of the basis functions
+    /// * `span` - index of the knot span containing `u` (see `find_span`)
+    /// * `u` - the parameter value at which to evaluate the basis functions
+    /// * `n` - the highest order of derivative to compute
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use capstan::knotvec::KnotVec;
+    /// let knots = KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+    /// let ders = knots.basis_function_derivatives(2, knots.find_span(0.5), 0.5, 1);
+    /// assert_eq!(ders[0], vec![0.25, 0.5, 0.25]);
+    /// assert_eq!(ders[1], vec![-1.0, 0.0, 1.0]);
+    /// ```
+    pub fn basis_function_derivatives(
+        &self,
+        degree: usize,
+        span: usize,
+        u: N,
+        n: usize,
+    ) -> Vec<Vec<N>> {
+        let p = degree;
+        let mut ders = vec![vec![N::zero(); p + 1]; n + 1];
+
+        // basis functions and knot differences, stored in the upper and lower
+        // triangles of ndu respectively
+        let mut ndu = vec![vec![N::zero(); p + 1]; p + 1];
+        let mut left = vec![N::zero(); p + 1];
+        let mut right = vec![N::zero(); p + 1];
+        ndu[0][0] = N::one();
+        for j in 1..p + 1 {
+            left[j] = u - self.knots[span + 1 - j];
+            right[j] = self.knots[span + j] - u;
+            let mut saved = N::zero();
+            for r in 0..j {
+                ndu[j][r] = right[r + 1] + left[j - r];
+                let temp = ndu[r][j - 1] / ndu[j][r];
+                ndu[r][j] = saved + right[r + 1] * temp;
+                saved = left[j - r] * temp;
+            }
+            ndu[j][j] = saved;
+        }
+        for j in 0..p + 1 {
+            ders[0][j] = ndu[j][p];
+        }
+
+        // derivatives; the rows of `a` alternate between the current and
+        // previous derivative orders
+        let n_nonzero = n.min(p);
+        let mut a = vec![vec![N::zero(); p + 1]; 2];
+        for r in 0..p + 1 {
+            let (mut s1, mut s2) = (0, 1);
+            a[0][0] = N::one();
+            for k in 1..n_nonzero + 1 {
+                let mut d = N::zero();
+                let rk = r as isize - k as isize;
+                let pk = p - k;
+                if r >= k {
+                    let rk = rk as usize;
+                    a[s2][0] = a[s1][0] / ndu[pk + 1][rk];
+                    d = a[s2][0] * ndu[rk][pk];
+                }
+                let j1 = if rk >= -1 { 1 } else { (-rk) as usize };
+                let j2 = if r <= pk + 1 { k - 1 } else { p - r };
+                for j in j1..j2 + 1 {
+                    let rkj = (rk + j as isize) as usize;
+                    a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][rkj];
+                    d += a[s2][j] * ndu[rkj][pk];
+                }
+                if r <= pk {
+                    a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                    d += a[s2][k] * ndu[r][pk];
+                }
+                ders[k][r] = d;
+                std::mem::swap(&mut s1, &mut s2);
+            }
+        }
+
+        // multiply through by the factors p!/(p-k)!
+        let mut factor = N::from_usize(p).unwrap();
+        for (k, row) in ders.iter_mut().enumerate().take(n_nonzero + 1).skip(1) {
+            for d in row.iter_mut() {
+                *d *= factor;
+            }
+            factor *= N::from_usize(p - k).unwrap();
+        }
+
+        ders
+    }
 }
 
 impl<N: ScalarT> Index<usize> for KnotVec<N> {
@@ -221,7 +414,7 @@ mod tests {
     fn new() {
         let knots = KnotVec::new(vec![0.0, 0.0, 0.5, 1.0, 1.0]).unwrap();
         assert_eq!(knots.len(), 5);
-        assert_eq!(knots.is_empty(), false);
+        assert!(!knots.is_empty());
         assert_eq!(knots[0], 0.0);
         assert_eq!(knots[1], 0.0);
         assert_eq!(knots[2], 0.5);
@@ -242,6 +435,9 @@ mod tests {
         assert!(knots2.is_clamped(1));
         assert!(!knots2.is_clamped(2));
         assert!(!knots2.is_clamped(100));
+
+        let knots3 = KnotVec::new(vec![0.0, 0.0, 1.0, 2.0, 2.0, 2.0]).unwrap();
+        assert!(!knots3.is_clamped(2));
     }
 
     /// Test clamping the paramter.
@@ -296,6 +492,22 @@ mod tests {
         knots.find_span(5.5);
     }
 
+    /// Test evaluating basis functions and their derivatives.
+    #[test]
+    fn basis_function_derivatives() {
+        let knots =
+            KnotVec::new(vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0]).unwrap();
+        let span = knots.find_span(2.5);
+        assert_eq!(span, 4);
+        let ders = knots.basis_function_derivatives(2, span, 2.5, 3);
+
+        // values from The NURBS Book, Example 2.3 and 2.4
+        assert_eq!(ders[0], vec![1.0 / 8.0, 6.0 / 8.0, 1.0 / 8.0]);
+        assert_eq!(ders[1], vec![-0.5, 0.0, 0.5]);
+        assert_eq!(ders[2], vec![1.0, -2.0, 1.0]);
+        assert_eq!(ders[3], vec![0.0, 0.0, 0.0]);
+    }
+
     prop_compose! {
         fn arb_knotvec(min_len: usize)
                       (len in min_len..128)
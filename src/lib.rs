@@ -1,5 +1,5 @@
-#![feature(is_sorted)]
-
 pub mod algebra;
+pub mod bounds;
+pub mod conic;
 pub mod curve;
 pub mod knotvec;
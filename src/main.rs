@@ -8,6 +8,7 @@ use svg::node::element::Path;
 use svg::node::Node;
 use svg::Document;
 
+use capstan::conic;
 use capstan::knotvec::KnotVec;
 type Curve = capstan::curve::Curve<f32, Vector2<f32>>;
 
@@ -37,8 +38,8 @@ fn reed_leaf_example(filename: &str) {
 fn circle_example(filename: &str) {
     let radius = 130.0;
 
-    let mut nurbs_circle = unit_circle();
-    nurbs_circle.uniform_scale(radius);
+    let nurbs_circle =
+        conic::circle(&Vector2::zeros(), &Vector2::x(), &Vector2::y(), radius).unwrap();
     let nurbs_group =
         curve_and_control_polygon(&nurbs_circle, 256).set("transform", "translate(150, 150)");
 
@@ -118,21 +119,25 @@ fn curve_path(curve: &Curve, n_divisions: usize) -> Path {
 }
 
 fn curve_path_data(curve: &Curve, n_divisions: usize) -> path::Data {
-    let min_u = curve.knots().min_u();
-    let max_u = curve.knots().max_u();
-    let u_range = max_u - min_u;
+    // points are spaced evenly in distance along the curve, rather than in
+    // the parameter
+    let tolerance = 1e-3;
+    let min_u = curve.min_u();
+    let table = curve.arc_length_table(tolerance);
+    let length = table.length();
     let range_denom = n_divisions as f32;
 
     let mut commands = Vec::with_capacity(n_divisions + 1);
     commands.push(path::Command::Move(
         path::Position::Absolute,
-        path::Parameters::from(eval_curve_2d(&curve, min_u)),
+        path::Parameters::from(eval_curve_2d(curve, min_u)),
     ));
     for i in 1..(n_divisions + 1) {
-        let u = min_u + (i as f32) * u_range / range_denom;
+        let s = (i as f32) * length / range_denom;
+        let u = table.param_at_length(s).unwrap();
         commands.push(path::Command::Line(
             path::Position::Absolute,
-            path::Parameters::from(eval_curve_2d(&curve, u)),
+            path::Parameters::from(eval_curve_2d(curve, u)),
         ))
     }
 
@@ -190,28 +195,6 @@ fn eval_curve_2d(curve: &Curve, u: f32) -> (f32, f32) {
     (pt_3d.x, pt_3d.y)
 }
 
-fn unit_circle() -> Curve {
-    let r = f32::sqrt(2.0) / 2.0;
-    let degree = 2;
-    let control_points = vec![
-        Vector2::new(1.0, 0.0),
-        Vector2::new(1.0, 1.0),
-        Vector2::new(0.0, 1.0),
-        Vector2::new(-1.0, 1.0),
-        Vector2::new(-1.0, 0.0),
-        Vector2::new(-1.0, -1.0),
-        Vector2::new(0.0, -1.0),
-        Vector2::new(1.0, -1.0),
-        Vector2::new(1.0, 0.0),
-    ];
-    let weights = vec![1.0, r, 1.0, r, 1.0, r, 1.0, r, 1.0];
-    let knots = KnotVec::new(vec![
-        0.0, 0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1.0, 1.0, 1.0,
-    ])
-    .unwrap();
-    Curve::new(degree, control_points, weights, knots).unwrap()
-}
-
 fn reed_leaf() -> Curve {
     let degree = 3;
     let control_points = vec![